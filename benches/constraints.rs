@@ -0,0 +1,91 @@
+//! Proving-mode synthesis of a chain of two-to-one compressions, the shape of a
+//! Merkle path, through the `FpVar` permutations and the native witness ones.
+
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::AllocVar};
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef, SynthesisError};
+use ark_std::{test_rng, UniformRand, Zero};
+use arkworks_mimc::{constraints::MiMCVar, MiMC, MiMCParameters, PermutationType};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const DEPTH: usize = 16;
+
+#[derive(Clone, Default)]
+struct Feistel;
+
+impl MiMCParameters for Feistel {
+    const ROUNDS: usize = 220;
+    const EXPONENT: usize = 5;
+}
+
+#[derive(Clone, Default)]
+struct NonFeistel;
+
+impl MiMCParameters for NonFeistel {
+    const ROUNDS: usize = 91;
+    const EXPONENT: usize = 7;
+}
+
+fn mimc<P: MiMCParameters>() -> MiMC<Fr, P> {
+    let rng = &mut test_rng();
+    let round_keys = (0..P::ROUNDS)
+        .map(|i| match i {
+            0 => Fr::zero(),
+            _ => Fr::rand(rng),
+        })
+        .collect();
+    MiMC::new(1, Fr::zero(), round_keys)
+}
+
+/// Synthesize and finalize the path, as proving does before building the witness
+fn path<P: MiMCParameters>(
+    mimc: &MiMC<Fr, P>,
+    permutation_type: PermutationType,
+    native_witness: bool,
+) -> Result<ConstraintSystemRef<Fr>, SynthesisError> {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let mimc_var = MiMCVar::new_witness(cs.clone(), || Ok(mimc.clone()))?;
+    let mut node = FpVar::new_witness(cs.clone(), || Ok(Fr::from(1)))?;
+    for i in 0..DEPTH {
+        let sibling = FpVar::new_witness(cs.clone(), || Ok(Fr::from(i as u64)))?;
+        let state = vec![node, sibling];
+        node = match (permutation_type, native_witness) {
+            (PermutationType::Feistel, false) => mimc_var.permute_feistel(state),
+            (PermutationType::Feistel, true) => mimc_var.permute_feistel_native_witness(state)?,
+            (PermutationType::NonFeistel, false) => mimc_var.permute_non_feistel(state),
+            (PermutationType::NonFeistel, true) => {
+                mimc_var.permute_non_feistel_native_witness(state)?
+            }
+        }
+        .swap_remove(0);
+    }
+    cs.finalize();
+    Ok(cs)
+}
+
+fn bench<P: MiMCParameters>(c: &mut Criterion, permutation_type: PermutationType) {
+    let mimc = mimc::<P>();
+    for native_witness in [false, true] {
+        assert!(path(&mimc, permutation_type, native_witness)
+            .unwrap()
+            .is_satisfied()
+            .unwrap());
+    }
+    let mut group = c.benchmark_group(format!("{permutation_type:?} path of {DEPTH}"));
+    group.sample_size(10);
+    group.bench_function("FpVar", |b| {
+        b.iter(|| path(&mimc, permutation_type, false).unwrap())
+    });
+    group.bench_function("native witness", |b| {
+        b.iter(|| path(&mimc, permutation_type, true).unwrap())
+    });
+    group.finish();
+}
+
+fn constraints(c: &mut Criterion) {
+    bench::<Feistel>(c, PermutationType::Feistel);
+    bench::<NonFeistel>(c, PermutationType::NonFeistel);
+}
+
+criterion_group!(benches, constraints);
+criterion_main!(benches);
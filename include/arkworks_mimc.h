@@ -0,0 +1,98 @@
+#ifndef ARKWORKS_MIMC_H
+#define ARKWORKS_MIMC_H
+
+/* Generated by cbindgen from src/ffi.rs, regenerate with `BLESS=1 cargo test --features ffi --test ffi` */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Bytes of one field element in every bundled field
+ */
+#define MIMC_FIELD_BYTES 32
+
+/**
+ * Largest `num_outputs` of [`mimc_hash`]
+ */
+#define MIMC_MAX_OUTPUTS (1 << 16)
+
+typedef enum MiMCStatus {
+  MiMCStatus_Ok = 0,
+  /**
+   * A required pointer is null
+   */
+  MiMCStatus_NullPointer = 1,
+  /**
+   * The preset name is not UTF-8 or not a bundled preset
+   */
+  MiMCStatus_UnknownPreset = 2,
+  /**
+   * Bytes that are not a field element below the modulus
+   */
+  MiMCStatus_InvalidFieldElement = 3,
+  /**
+   * No inputs, no or more than `MIMC_MAX_OUTPUTS` outputs, or more input bytes
+   * than a buffer can hold
+   */
+  MiMCStatus_InvalidLength = 4,
+  /**
+   * Internal error, the outputs are unspecified
+   */
+  MiMCStatus_Panic = 5,
+} MiMCStatus;
+
+/**
+ * Opaque hash of one preset, from [`mimc_new`] and released with [`mimc_free`]
+ */
+typedef struct MiMCHandle MiMCHandle;
+
+/**
+ * Create a handle for the preset named `preset`, such as `"mimc-7-91-bn254"`,
+ * with key zero, and store it in `*out`.
+ *
+ * # Safety
+ *
+ * `preset` is a NUL-terminated string and `out` is valid for writes.
+ */
+enum MiMCStatus mimc_new(const char *preset, struct MiMCHandle **out);
+
+/**
+ * Release a handle from [`mimc_new`], null is ignored.
+ *
+ * # Safety
+ *
+ * `handle` is null or from [`mimc_new`] and not yet freed.
+ */
+void mimc_free(struct MiMCHandle *handle);
+
+/**
+ * Permute `num_inputs` field elements with the preset's permutation type and
+ * write the first `num_outputs` elements of the state to `outputs`, at most
+ * [`MIMC_MAX_OUTPUTS`].
+ *
+ * # Safety
+ *
+ * `handle` is from [`mimc_new`], `inputs` holds `num_inputs * MIMC_FIELD_BYTES`
+ * bytes and `outputs` has room for `num_outputs * MIMC_FIELD_BYTES`.
+ */
+enum MiMCStatus mimc_hash(const struct MiMCHandle *handle,
+                          const uint8_t *inputs,
+                          size_t num_inputs,
+                          uint8_t *outputs,
+                          size_t num_outputs);
+
+/**
+ * Two-to-one compression of `left` and `right` into `out`, as
+ * [`DynMiMC::compress`].
+ *
+ * # Safety
+ *
+ * `handle` is from [`mimc_new`] and the other pointers hold
+ * `MIMC_FIELD_BYTES` bytes each.
+ */
+enum MiMCStatus mimc_compress(const struct MiMCHandle *handle,
+                              const uint8_t *left,
+                              const uint8_t *right,
+                              uint8_t *out);
+
+#endif  /* ARKWORKS_MIMC_H */
@@ -0,0 +1,164 @@
+//! Generate round keys and write them as a `src/params` module, a circomlibjs-style
+//! JSON array or a circom template.
+//!
+//! ```text
+//! mimc-paramgen --field bn254 --exponent 7 --kind non-feistel > src/params/mimc_7_91_bn254.rs
+//! mimc-paramgen --field bls12-377 --exponent 11 --kind feistel --format circom --seed in-house
+//! ```
+
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use ark_ff::PrimeField;
+use arkworks_mimc::{
+    codegen::{circom_template, preset_name, rust_module},
+    encoding::{save_circomlib_constants, NumberEncoding},
+    paramgen::{generate_round_keys_with, ConstantDerivation},
+    validate_parameters, PermutationType,
+};
+use clap::{Parser, ValueEnum};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Field {
+    Bn254,
+    #[value(name = "bls12-381")]
+    Bls12_381,
+    #[value(name = "bls12-377")]
+    Bls12_377,
+}
+
+impl Field {
+    fn name(self) -> &'static str {
+        match self {
+            Field::Bn254 => "bn254",
+            Field::Bls12_381 => "bls12-381",
+            Field::Bls12_377 => "bls12-377",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Kind {
+    Feistel,
+    NonFeistel,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Derivation {
+    Circomlib,
+    Gnark,
+    Iden3,
+    Shake,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Module in the style of `src/params`
+    Rust,
+    /// circomlibjs-style array of round keys
+    Json,
+    /// circom template
+    Circom,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Encoding {
+    Decimal,
+    Hex,
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Generate MiMC round keys as Rust, JSON or circom sources")]
+struct Args {
+    #[arg(long, value_enum)]
+    field: Field,
+    #[arg(long)]
+    exponent: usize,
+    #[arg(long, value_enum)]
+    kind: Kind,
+    /// Defaults to circomlib's `mimcsponge` for feistel and `mimc` otherwise
+    #[arg(long)]
+    seed: Option<String>,
+    #[arg(long, value_enum, default_value = "circomlib")]
+    derivation: Derivation,
+    #[arg(long, value_enum, default_value = "rust")]
+    format: Format,
+    /// Number encoding of the JSON format
+    #[arg(long, value_enum, default_value = "decimal")]
+    encoding: Encoding,
+    /// Template name of the circom format, defaults to the preset name
+    #[arg(long)]
+    template: Option<String>,
+    /// Defaults to stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    match args.field {
+        Field::Bn254 => generate::<ark_bn254::Fr>(&args),
+        Field::Bls12_381 => generate::<ark_bls12_381::Fr>(&args),
+        Field::Bls12_377 => generate::<ark_bls12_377::Fr>(&args),
+    }
+}
+
+fn generate<F: PrimeField>(args: &Args) -> Result<(), Box<dyn Error>> {
+    let permutation_type = match args.kind {
+        Kind::Feistel => PermutationType::Feistel,
+        Kind::NonFeistel => PermutationType::NonFeistel,
+    };
+    let seed = match (&args.seed, permutation_type) {
+        (Some(seed), _) => seed.as_str(),
+        (None, PermutationType::Feistel) => "mimcsponge",
+        (None, PermutationType::NonFeistel) => "mimc",
+    };
+    let derivation = match args.derivation {
+        Derivation::Circomlib => ConstantDerivation::CircomlibKeccak,
+        Derivation::Gnark => ConstantDerivation::GnarkSha3,
+        Derivation::Iden3 => ConstantDerivation::Iden3Mimc7,
+        Derivation::Shake => ConstantDerivation::ShakeXof,
+    };
+    let (rounds, round_keys) =
+        generate_round_keys_with::<F>(permutation_type, args.exponent, seed.as_bytes(), &derivation)?;
+    if let Err(e) = validate_parameters::<F>(args.exponent, rounds) {
+        eprintln!("warning: {e}");
+    }
+
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    match args.format {
+        Format::Rust => write!(
+            output,
+            "{}",
+            rust_module(args.field.name(), permutation_type, args.exponent, &round_keys)
+        )?,
+        Format::Json => {
+            let encoding = match args.encoding {
+                Encoding::Decimal => NumberEncoding::Decimal,
+                Encoding::Hex => NumberEncoding::Hex,
+            };
+            save_circomlib_constants(&mut output, &round_keys, encoding)?;
+            writeln!(output)?;
+        }
+        Format::Circom => {
+            let template = args.template.clone().unwrap_or_else(|| {
+                preset_name(args.field.name(), args.exponent, rounds)
+                    .replace('-', "_")
+                    .to_uppercase()
+            });
+            write!(
+                output,
+                "{}",
+                circom_template(&template, permutation_type, args.exponent, &round_keys)
+            )?
+        }
+    }
+    Ok(())
+}
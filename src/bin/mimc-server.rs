@@ -0,0 +1,368 @@
+//! Hash, batch-hash and Merkle endpoints over JSON for one bundled preset, on a
+//! Unix socket or a localhost HTTP port.
+//!
+//! ```text
+//! mimc-server --preset mimc-5-220-bn254 --listen 127.0.0.1:8545
+//! mimc-server --preset mimc-7-91-bn254 --unix /run/mimc.sock
+//! ```
+//!
+//! Every endpoint but `GET /info` takes a JSON `POST` body. Field elements are
+//! decimal or `0x` hex strings, or byte arrays for the byte encodings, and are
+//! written back with the request's `encoding`, decimal by default.
+//!
+//! - `GET /info`: the preset, permutation type, exponent and round count
+//! - `POST /hash`: `{"inputs": [..], "mode": "permute" | "compress", "num_outputs": 1}`
+//!   to `{"outputs": [..]}`
+//! - `POST /hash/batch`: `{"inputs": [[..], ..], ..}` to `{"outputs": [[..], ..]}`
+//! - `POST /merkle`: `{"depth": 20, "leaves": [..], "proofs": [0, 7]}` to
+//!   `{"root": .., "proofs": [..]}` with proofs as `encoding::MerkleProofDocument`
+//! - `POST /merkle/verify`: a proof document to `{"valid": true | false}`
+//!
+//! Errors are answered with status 400, 404, 405 or 413 and `{"error": ".."}`.
+
+use std::{
+    error::Error,
+    io::Read,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+};
+
+use ark_ff::PrimeField;
+use arkworks_mimc::{
+    encoding::{MerkleProofDocument, Number, NumberEncoding},
+    merkle::{MerkleTree, MAX_DEPTH},
+    params, DynMiMC, MiMCError, PermutationType,
+};
+use clap::{builder::RangedU64ValueParser, Parser, ValueEnum};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Permutation {
+    Feistel,
+    NonFeistel,
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Serve MiMC hashes and Merkle proofs over JSON")]
+struct Args {
+    #[arg(long)]
+    preset: String,
+    /// Defaults to the preset's permutation type
+    #[arg(long, value_enum)]
+    permutation: Option<Permutation>,
+    #[arg(long, default_value = "0")]
+    key: String,
+    /// Loopback address and port, port 0 picks a free one
+    #[arg(long, conflicts_with = "unix", required_unless_present = "unix")]
+    listen: Option<SocketAddr>,
+    /// Path of a Unix socket to create
+    #[arg(long)]
+    unix: Option<PathBuf>,
+    /// Largest tree depth `POST /merkle` builds, at most `merkle::MAX_DEPTH`
+    #[arg(
+        long,
+        default_value_t = 20,
+        value_parser = RangedU64ValueParser::<usize>::new().range(..=MAX_DEPTH as u64)
+    )]
+    max_depth: usize,
+    /// Largest number of inputs or leaves in one request
+    #[arg(long, default_value_t = 1 << 20)]
+    max_inputs: usize,
+    /// Largest request body in bytes
+    #[arg(long, default_value_t = 1 << 27)]
+    max_body: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Mode {
+    #[default]
+    Permute,
+    Compress,
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HashRequest<T> {
+    inputs: T,
+    #[serde(default)]
+    mode: Mode,
+    #[serde(default = "one")]
+    num_outputs: usize,
+    #[serde(default)]
+    encoding: NumberEncoding,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MerkleRequest {
+    depth: usize,
+    leaves: Vec<Number>,
+    #[serde(default)]
+    proofs: Vec<usize>,
+    #[serde(default)]
+    encoding: NumberEncoding,
+}
+
+/// Error answered with a status code
+struct Reply(u16, String);
+
+impl<E: Error> From<E> for Reply {
+    fn from(e: E) -> Self {
+        Reply(400, e.to_string())
+    }
+}
+
+struct Service<F: PrimeField> {
+    preset: String,
+    mimc: DynMiMC<F>,
+    max_depth: usize,
+    max_inputs: usize,
+    max_body: u64,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    if args.preset.ends_with("-bn254") {
+        serve::<ark_bn254::Fr>(&args)
+    } else if args.preset.ends_with("-bls12-381") {
+        serve::<ark_bls12_381::Fr>(&args)
+    } else if args.preset.ends_with("-bls12-377") {
+        serve::<ark_bls12_377::Fr>(&args)
+    } else {
+        Err(MiMCError::UnknownPreset(args.preset.clone()).into())
+    }
+}
+
+fn serve<F: PrimeField>(args: &Args) -> Result<(), Box<dyn Error>> {
+    let preset: DynMiMC<F> = params::by_name(&args.preset)?;
+    let k = NumberEncoding::Decimal
+        .decode(&Number::Text(args.key.clone()))
+        .ok_or_else(|| MiMCError::InvalidNumber(args.key.clone()))?;
+    let service = Service {
+        preset: args.preset.clone(),
+        mimc: DynMiMC::try_new_unvalidated(
+            args.permutation
+                .map_or(preset.permutation_type, |p| match p {
+                    Permutation::Feistel => PermutationType::Feistel,
+                    Permutation::NonFeistel => PermutationType::NonFeistel,
+                }),
+            preset.exponent(),
+            1,
+            k,
+            preset.round_keys().to_vec(),
+        )?,
+        max_depth: args.max_depth,
+        max_inputs: args.max_inputs,
+        max_body: args.max_body,
+    };
+
+    let server = match (&args.listen, &args.unix) {
+        (Some(addr), _) if !is_loopback(addr.ip()) => {
+            return Err(format!("{addr} is not a loopback address").into())
+        }
+        (Some(addr), _) => Server::http(addr).map_err(|e| e.to_string())?,
+        #[cfg(unix)]
+        (None, Some(path)) => Server::http_unix(path).map_err(|e| e.to_string())?,
+        _ => return Err("no address to listen on".into()),
+    };
+    // Read by clients that asked for port 0
+    match server.server_addr().to_ip() {
+        Some(addr) => println!("listening on {addr}"),
+        None => println!("listening on {}", args.unix.as_ref().unwrap().display()),
+    }
+
+    for request in server.incoming_requests() {
+        service.respond(request);
+    }
+    Ok(())
+}
+
+fn is_loopback(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_loopback(),
+        IpAddr::V6(ip) => {
+            ip.is_loopback() || ip.to_ipv4_mapped().is_some_and(|ip| ip.is_loopback())
+        }
+    }
+}
+
+impl<F: PrimeField> Service<F> {
+    fn respond(&self, mut request: Request) {
+        let mut body = vec![];
+        // One byte past the limit tells a body at the limit from a longer one
+        let read = request
+            .as_reader()
+            .take(self.max_body.saturating_add(1))
+            .read_to_end(&mut body);
+        let (status, value) = match read {
+            Ok(_) if body.len() as u64 > self.max_body => (
+                413,
+                json!({ "error": format!("body is larger than {} bytes", self.max_body) }),
+            ),
+            Ok(_) => match self.handle(request.method(), request.url(), &body) {
+                Ok(value) => (200, value),
+                Err(Reply(status, error)) => (status, json!({ "error": error })),
+            },
+            Err(e) => (400, json!({ "error": e.to_string() })),
+        };
+        let response = Response::from_string(value.to_string())
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes("Content-Type", "application/json").expect("valid header"),
+            );
+        if let Err(e) = request.respond(response) {
+            eprintln!("error: {e}");
+        }
+    }
+
+    fn handle(&self, method: &Method, url: &str, body: &[u8]) -> Result<Value, Reply> {
+        match (method, url) {
+            (Method::Get, "/info") => Ok(json!({
+                "preset": self.preset,
+                "permutation_type": self.mimc.permutation_type,
+                "exponent": self.mimc.exponent(),
+                "rounds": self.mimc.rounds(),
+            })),
+            (Method::Post, "/hash") => self.hash(parse(body)?),
+            (Method::Post, "/hash/batch") => self.hash_batch(parse(body)?),
+            (Method::Post, "/merkle") => self.merkle(parse(body)?),
+            (Method::Post, "/merkle/verify") => self.verify(parse(body)?),
+            (_, "/info" | "/hash" | "/hash/batch" | "/merkle" | "/merkle/verify") => {
+                Err(Reply(405, format!("{method} not allowed on {url}")))
+            }
+            _ => Err(Reply(404, format!("no endpoint {url}"))),
+        }
+    }
+
+    fn hash(&self, request: HashRequest<Vec<Number>>) -> Result<Value, Reply> {
+        let mimc = self.with_outputs(request.mode, request.num_outputs)?;
+        let inputs = self.decode_all(request.encoding, &request.inputs)?;
+        let outputs = match request.mode {
+            Mode::Permute => mimc.permute(inputs),
+            Mode::Compress => vec![mimc.compress_inputs(&inputs)?],
+        };
+        Ok(json!({ "outputs": encode_all(request.encoding, &outputs) }))
+    }
+
+    fn hash_batch(&self, request: HashRequest<Vec<Vec<Number>>>) -> Result<Value, Reply> {
+        let mimc = self.with_outputs(request.mode, request.num_outputs)?;
+        self.check_size(request.inputs.iter().map(Vec::len).sum())?;
+        let inputs = request
+            .inputs
+            .iter()
+            .map(|inputs| self.decode_all(request.encoding, inputs))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = match request.mode {
+            Mode::Permute => mimc.hash_many(&inputs),
+            Mode::Compress => mimc
+                .compress_many(&inputs)?
+                .into_iter()
+                .map(|output| vec![output])
+                .collect(),
+        };
+        Ok(json!({
+            "outputs": outputs
+                .iter()
+                .map(|outputs| encode_all(request.encoding, outputs))
+                .collect::<Vec<_>>()
+        }))
+    }
+
+    fn merkle(&self, request: MerkleRequest) -> Result<Value, Reply> {
+        if request.depth > self.max_depth {
+            return Err(Reply(
+                400,
+                format!(
+                    "depth {} is above the maximum of {}",
+                    request.depth, self.max_depth
+                ),
+            ));
+        }
+        let leaves = self.decode_all(request.encoding, &request.leaves)?;
+        let tree = MerkleTree::new_dyn(&self.mimc, request.depth, &leaves)?;
+        let proofs = request
+            .proofs
+            .iter()
+            .map(|index| {
+                Ok(MerkleProofDocument::from_proof(
+                    &tree.proof(*index)?,
+                    &tree.root(),
+                    Some(&self.preset),
+                    Some(self.mimc.permutation_type),
+                    request.encoding,
+                ))
+            })
+            .collect::<Result<Vec<_>, MiMCError>>()?;
+        Ok(json!({
+            "root": request.encoding.encode(&tree.root()),
+            "proofs": proofs,
+        }))
+    }
+
+    fn verify(&self, document: MerkleProofDocument) -> Result<Value, Reply> {
+        if let Some(preset) = document.preset_id.as_ref().filter(|id| **id != self.preset) {
+            return Err(MiMCError::PresetMismatch(preset.clone()).into());
+        }
+        if document.siblings.len() > self.max_depth {
+            return Err(Reply(
+                400,
+                format!("proof is deeper than {}", self.max_depth),
+            ));
+        }
+        let (proof, root) = document.to_proof::<F>()?;
+        let valid = document
+            .permutation_type
+            .is_none_or(|permutation_type| permutation_type == self.mimc.permutation_type)
+            && proof.verify_dyn(&self.mimc, root);
+        Ok(json!({ "valid": valid }))
+    }
+
+    /// The hash with `num_outputs` outputs, one for compressions
+    fn with_outputs(&self, mode: Mode, num_outputs: usize) -> Result<DynMiMC<F>, Reply> {
+        if num_outputs == 0 || num_outputs > self.max_inputs {
+            return Err(Reply(
+                400,
+                format!("invalid number of outputs {num_outputs}"),
+            ));
+        }
+        let mut mimc = self.mimc.clone();
+        if let Mode::Permute = mode {
+            mimc.num_outputs = num_outputs;
+        }
+        Ok(mimc)
+    }
+
+    fn check_size(&self, len: usize) -> Result<(), Reply> {
+        match len > self.max_inputs {
+            true => Err(Reply(400, format!("more than {} inputs", self.max_inputs))),
+            false => Ok(()),
+        }
+    }
+
+    fn decode_all(&self, encoding: NumberEncoding, numbers: &[Number]) -> Result<Vec<F>, Reply> {
+        self.check_size(numbers.len())?;
+        numbers
+            .iter()
+            .map(|number| {
+                encoding
+                    .decode(number)
+                    .ok_or_else(|| MiMCError::InvalidNumber(number.to_string()).into())
+            })
+            .collect()
+    }
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, Reply> {
+    serde_json::from_slice(body).map_err(|e| MiMCError::InvalidJson(e.to_string()).into())
+}
+
+fn encode_all<F: PrimeField>(encoding: NumberEncoding, values: &[F]) -> Vec<Number> {
+    values.iter().map(|f| encoding.encode(f)).collect()
+}
@@ -0,0 +1,628 @@
+//! Hash with the bundled presets and check files of expected outputs.
+//!
+//! ```text
+//! mimc hash --preset mimc-5-220-bn254 --mode compress 1 2
+//! echo "1 2" | mimc hash --preset mimc-7-91-bn254 --encoding hex
+//! mimc hash --preset mimc-7-91-bn254 --input-format bytes < message.bin
+//! mimc verify-vectors vectors/mimc.json
+//! mimc merkle --preset mimc-5-220-bn254 --depth 20 --proof 0 --proof 7 -o proofs.json leaves.txt
+//! ```
+
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use ark_ff::{BigInteger, PrimeField};
+use arkworks_mimc::{
+    encoding::{MerkleProofDocument, Number, NumberEncoding},
+    merkle::{MerkleTree, MAX_DEPTH},
+    params,
+    utils::to_field_elements,
+    DynMiMC, MiMCError, PermutationType,
+};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+
+/// Run `$body` with `$field` as the scalar field named by the suffix of `$preset`
+macro_rules! with_field {
+    ($preset:expr, $field:ident => $body:expr) => {{
+        let preset: &str = $preset;
+        if preset.ends_with("-bn254") {
+            type $field = ark_bn254::Fr;
+            $body
+        } else if preset.ends_with("-bls12-381") {
+            type $field = ark_bls12_381::Fr;
+            $body
+        } else if preset.ends_with("-bls12-377") {
+            type $field = ark_bls12_377::Fr;
+            $body
+        } else {
+            Err(MiMCError::UnknownPreset(preset.to_string()).into())
+        }
+    }};
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Hash with MiMC presets and check test vectors")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Hash the inputs given as arguments, or read from stdin if there are none
+    Hash(HashArgs),
+    /// List the bundled presets
+    Presets,
+    /// Check a JSON array of expected outputs, such as `vectors/mimc.json`
+    VerifyVectors { file: PathBuf },
+    /// Print the root of a tree of compressions and write inclusion proofs as JSON
+    Merkle(MerkleArgs),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Mode {
+    /// Permute all inputs, as the CRH does
+    #[default]
+    Permute,
+    /// Two-to-one compression of exactly two inputs
+    Compress,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Permutation {
+    Feistel,
+    NonFeistel,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum InputFormat {
+    Decimal,
+    /// With or without `0x`
+    Hex,
+    /// Hex arguments or raw stdin, split into field elements like `MiMCNonFeistelCRH`
+    Bytes,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Encoding {
+    Decimal,
+    Hex,
+    LeBytes,
+    BeBytes,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LeafFormat {
+    /// One decimal or `0x` hex field element per line
+    Text,
+    /// Little-endian field elements of the field's byte length, back to back
+    Binary,
+}
+
+impl From<Permutation> for PermutationType {
+    fn from(permutation: Permutation) -> Self {
+        match permutation {
+            Permutation::Feistel => PermutationType::Feistel,
+            Permutation::NonFeistel => PermutationType::NonFeistel,
+        }
+    }
+}
+
+impl From<Encoding> for NumberEncoding {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Decimal => NumberEncoding::Decimal,
+            Encoding::Hex => NumberEncoding::Hex,
+            Encoding::LeBytes => NumberEncoding::LeBytes,
+            Encoding::BeBytes => NumberEncoding::BeBytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+struct HasherArgs {
+    #[arg(long)]
+    preset: String,
+    /// Defaults to the preset's permutation type
+    #[arg(long, value_enum)]
+    permutation: Option<Permutation>,
+    #[arg(long, default_value = "0")]
+    key: String,
+}
+
+#[derive(Debug, clap::Args)]
+struct HashArgs {
+    #[command(flatten)]
+    hasher: HasherArgs,
+    #[arg(long, value_enum, default_value = "permute")]
+    mode: Mode,
+    /// Number of outputs of `permute`
+    #[arg(long, default_value_t = 1)]
+    outputs: usize,
+    #[arg(long, value_enum, default_value = "decimal")]
+    input_format: InputFormat,
+    /// Encoding of the printed outputs
+    #[arg(long, value_enum, default_value = "decimal")]
+    encoding: Encoding,
+    inputs: Vec<String>,
+}
+
+#[derive(Debug, clap::Args)]
+struct MerkleArgs {
+    #[command(flatten)]
+    hasher: HasherArgs,
+    /// The tree has `2^depth` leaves, padded with zeros
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(..=MAX_DEPTH as u64))]
+    depth: usize,
+    #[arg(long, value_enum, default_value = "text")]
+    leaf_format: LeafFormat,
+    /// Index of a leaf to prove, may be repeated
+    #[arg(long = "proof")]
+    proofs: Vec<usize>,
+    /// Encoding of the root and the proofs
+    #[arg(long, value_enum, default_value = "decimal")]
+    encoding: Encoding,
+    /// File for the JSON array of proofs, defaults to stdout after the root
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Defaults to stdin
+    leaves: Option<PathBuf>,
+}
+
+/// Hash inputs as field elements or as bytes
+#[derive(Debug, Clone)]
+enum Inputs {
+    Numbers(Vec<Number>),
+    Bytes(Vec<u8>),
+}
+
+/// One entry of a test vector file, with `bytes` as hex instead of `inputs`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Vector {
+    #[serde(default)]
+    description: String,
+    preset: String,
+    #[serde(default)]
+    mode: Mode,
+    #[serde(default)]
+    permutation_type: Option<PermutationType>,
+    #[serde(default)]
+    key: Option<Number>,
+    #[serde(default)]
+    inputs: Vec<Number>,
+    #[serde(default)]
+    bytes: Option<String>,
+    outputs: Vec<Number>,
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Hash(args) => hash_command(args),
+        Command::Presets => {
+            params::names().iter().for_each(|name| println!("{name}"));
+            Ok(true)
+        }
+        Command::VerifyVectors { file } => File::open(file)
+            .map_err(Into::into)
+            .and_then(|file| verify_vectors(file, &mut io::stdout())),
+        Command::Merkle(args) => merkle_command(args),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn hash_command(args: HashArgs) -> Result<bool, Box<dyn Error>> {
+    let inputs = match (args.input_format, args.inputs.is_empty()) {
+        (InputFormat::Bytes, true) => {
+            let mut bytes = vec![];
+            io::stdin().read_to_end(&mut bytes)?;
+            Inputs::Bytes(bytes)
+        }
+        (InputFormat::Bytes, false) => Inputs::Bytes(
+            args.inputs
+                .iter()
+                .map(|arg| decode_hex(arg))
+                .collect::<Result<Vec<_>, _>>()?
+                .concat(),
+        ),
+        (format, _) => {
+            let mut tokens = args.inputs;
+            if tokens.is_empty() {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                tokens = text.split_whitespace().map(str::to_string).collect();
+            }
+            Inputs::Numbers(
+                tokens
+                    .into_iter()
+                    .map(|token| match format {
+                        InputFormat::Hex if !token.starts_with("0x") => {
+                            Number::Text(format!("0x{token}"))
+                        }
+                        _ => Number::Text(token),
+                    })
+                    .collect(),
+            )
+        }
+    };
+    let hasher = &args.hasher;
+    let encoding = NumberEncoding::from(args.encoding);
+    let outputs: Vec<Number> = with_field!(&hasher.preset, F => {
+        hash::<F>(
+            &hasher.preset,
+            args.mode,
+            hasher.permutation.map(Into::into),
+            &Number::Text(hasher.key.clone()),
+            args.outputs,
+            &inputs,
+        )
+        .map(|outputs| outputs.iter().map(|f| encoding.encode(f)).collect())
+    })?;
+    for output in outputs {
+        println!("{}", display(&output));
+    }
+    Ok(true)
+}
+
+fn merkle_command(args: MerkleArgs) -> Result<bool, Box<dyn Error>> {
+    let mut leaves = vec![];
+    match &args.leaves {
+        Some(path) => File::open(path)?.read_to_end(&mut leaves)?,
+        None => io::stdin().read_to_end(&mut leaves)?,
+    };
+    let (root, proofs) = with_field!(&args.hasher.preset, F => merkle::<F>(&args, &leaves))?;
+    println!("{}", display(&root));
+    if !args.proofs.is_empty() {
+        let mut output: Box<dyn Write> = match &args.output {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(io::stdout()),
+        };
+        serde_json::to_writer_pretty(&mut output, &proofs)
+            .map_err(|e| MiMCError::InvalidJson(e.to_string()))?;
+        writeln!(output)?;
+    }
+    Ok(true)
+}
+
+/// The encoded root and a proof for every requested index
+fn merkle<F: PrimeField>(
+    args: &MerkleArgs,
+    leaves: &[u8],
+) -> Result<(Number, Vec<MerkleProofDocument>), Box<dyn Error>> {
+    let leaves: Vec<F> = match args.leaf_format {
+        LeafFormat::Text => std::str::from_utf8(leaves)
+            .map_err(|_| MiMCError::InvalidNumber("non UTF-8 leaf".to_string()))?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| decode(&Number::Text(line.to_string())))
+            .collect::<Result<_, _>>()?,
+        LeafFormat::Binary => {
+            let size = F::zero().into_bigint().to_bytes_le().len();
+            if !leaves.len().is_multiple_of(size) {
+                return Err(format!("binary leaves are not a multiple of {size} bytes").into());
+            }
+            leaves
+                .chunks(size)
+                .map(|chunk| {
+                    NumberEncoding::LeBytes
+                        .decode(&Number::Bytes(chunk.to_vec()))
+                        .ok_or_else(|| {
+                            MiMCError::InvalidNumber(display(&Number::Bytes(chunk.to_vec())))
+                        })
+                })
+                .collect::<Result<_, _>>()?
+        }
+    };
+    let hasher = &args.hasher;
+    let mimc = dyn_mimc::<F>(
+        &hasher.preset,
+        hasher.permutation.map(Into::into),
+        &Number::Text(hasher.key.clone()),
+        1,
+    )?;
+    let tree = MerkleTree::new_dyn(&mimc, args.depth, &leaves)?;
+    let encoding = NumberEncoding::from(args.encoding);
+    let proofs = args
+        .proofs
+        .iter()
+        .map(|index| {
+            Ok(MerkleProofDocument::from_proof(
+                &tree.proof(*index)?,
+                &tree.root(),
+                Some(&hasher.preset),
+                Some(mimc.permutation_type),
+                encoding,
+            ))
+        })
+        .collect::<Result<_, MiMCError>>()?;
+    Ok((encoding.encode(&tree.root()), proofs))
+}
+
+/// The preset's hash with the permutation type overridden and `key` as the key
+fn dyn_mimc<F: PrimeField>(
+    preset: &str,
+    permutation_type: Option<PermutationType>,
+    key: &Number,
+    num_outputs: usize,
+) -> Result<DynMiMC<F>, MiMCError> {
+    let preset: DynMiMC<F> = params::by_name(preset)?;
+    DynMiMC::try_new_unvalidated(
+        permutation_type.unwrap_or(preset.permutation_type),
+        preset.exponent(),
+        num_outputs,
+        decode(key)?,
+        preset.round_keys().to_vec(),
+    )
+}
+
+fn hash<F: PrimeField>(
+    preset: &str,
+    mode: Mode,
+    permutation_type: Option<PermutationType>,
+    key: &Number,
+    num_outputs: usize,
+    inputs: &Inputs,
+) -> Result<Vec<F>, Box<dyn Error>> {
+    let mimc = dyn_mimc(preset, permutation_type, key, num_outputs)?;
+    let inputs = match inputs {
+        Inputs::Numbers(numbers) => numbers.iter().map(decode).collect::<Result<Vec<F>, _>>()?,
+        Inputs::Bytes(bytes) => to_field_elements(bytes),
+    };
+    match mode {
+        Mode::Permute => Ok(mimc.permute(inputs)),
+        Mode::Compress => Ok(vec![mimc.compress_inputs(&inputs)?]),
+    }
+}
+
+/// Check every vector, printing one line per vector, and whether all passed
+fn verify_vectors(reader: impl Read, out: &mut impl Write) -> Result<bool, Box<dyn Error>> {
+    let vectors: Vec<Vector> =
+        serde_json::from_reader(reader).map_err(|e| MiMCError::InvalidJson(e.to_string()))?;
+    let mut passed = true;
+    for (index, vector) in vectors.iter().enumerate() {
+        let result = with_field!(&vector.preset, F => check_vector::<F>(vector));
+        match result {
+            Ok(None) => writeln!(out, "ok {index} {}", vector.description)?,
+            Ok(Some(mismatch)) => {
+                passed = false;
+                writeln!(out, "FAILED {index} {}: {mismatch}", vector.description)?
+            }
+            Err(e) => {
+                passed = false;
+                writeln!(out, "FAILED {index} {}: {e}", vector.description)?
+            }
+        }
+    }
+    Ok(passed)
+}
+
+/// Description of the first differing output or output count, if any
+fn check_vector<F: PrimeField>(vector: &Vector) -> Result<Option<String>, Box<dyn Error>> {
+    if vector.outputs.is_empty() {
+        return Ok(Some("no expected outputs".to_string()));
+    }
+    let inputs = match &vector.bytes {
+        Some(hex) => Inputs::Bytes(decode_hex(hex)?),
+        None => Inputs::Numbers(vector.inputs.clone()),
+    };
+    let expected = vector
+        .outputs
+        .iter()
+        .map(decode)
+        .collect::<Result<Vec<F>, _>>()?;
+    let actual = hash::<F>(
+        &vector.preset,
+        vector.mode,
+        vector.permutation_type,
+        vector
+            .key
+            .as_ref()
+            .unwrap_or(&Number::Text("0".to_string())),
+        expected.len(),
+        &inputs,
+    )?;
+    if expected.len() != actual.len() {
+        return Ok(Some(format!(
+            "expected {} outputs got {}",
+            expected.len(),
+            actual.len()
+        )));
+    }
+    Ok(expected
+        .iter()
+        .zip(&actual)
+        .position(|(e, a)| e != a)
+        .map(|i| format!("output {i} expected {} got {}", expected[i], actual[i])))
+}
+
+fn decode<F: PrimeField>(number: &Number) -> Result<F, MiMCError> {
+    NumberEncoding::Decimal
+        .decode(number)
+        .ok_or_else(|| MiMCError::InvalidNumber(number.to_string()))
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>, MiMCError> {
+    let hex = text.strip_prefix("0x").unwrap_or(text);
+    // Non-ASCII text would split a character between two digits
+    if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+        return Err(MiMCError::InvalidNumber(text.to_string()));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| MiMCError::InvalidNumber(text.to_string()))
+        })
+        .collect()
+}
+
+/// Strings as they are and bytes as hex
+fn display(number: &Number) -> String {
+    match number {
+        Number::Text(text) => text.clone(),
+        Number::Bytes(bytes) => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_ff::{BigInteger, PrimeField};
+
+    use arkworks_mimc::{
+        encoding::MerkleProofDocument, params, DynMiMC, MiMCError, PermutationType,
+    };
+    use clap::Parser;
+
+    use super::{
+        decode_hex, dyn_mimc, hash, merkle, verify_vectors, Cli, Command, Inputs, Mode, Number,
+    };
+
+    fn merkle_args(args: &str) -> super::MerkleArgs {
+        match Cli::parse_from(format!("mimc merkle {args}").split_whitespace()).command {
+            Command::Merkle(args) => args,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn bundled_vectors() {
+        let mut out = vec![];
+        assert!(
+            verify_vectors(include_str!("../../vectors/mimc.json").as_bytes(), &mut out).unwrap()
+        );
+        assert_eq!(String::from_utf8(out).unwrap().matches("ok ").count(), 3);
+    }
+
+    #[test]
+    fn mismatched_vector() {
+        let vectors = r#"[
+            {"preset": "mimc-7-91-bn254", "inputs": ["1"], "outputs": ["1"]},
+            {"preset": "mimc-7-91-bn254", "mode": "compress", "inputs": ["1"], "outputs": ["1"]},
+            {"preset": "mimc-7-91-bn254", "bytes": "0x01", "outputs": ["0x1"], "description": "bytes"},
+            {"preset": "mimc-7-91-bn254", "inputs": ["1"], "outputs": []},
+            {"preset": "mimc-7-91-bn254", "mode": "compress", "inputs": ["1", "0"], "outputs": [
+                "21581643069407877618298966131175370729897531221281133974758693417099906058024", "0"
+            ]},
+            {"preset": "mimc-7-91-bn254", "bytes": "aéb0", "outputs": ["1"]}
+        ]"#;
+        let mut out = vec![];
+        assert!(!verify_vectors(vectors.as_bytes(), &mut out).unwrap());
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("FAILED 0 : output 0 expected 1 got "));
+        assert!(out.contains("FAILED 1 : compression takes 2 inputs, got 1"));
+        assert!(out.contains("FAILED 2 bytes: output 0"));
+        assert!(out.contains("FAILED 3 : no expected outputs"));
+        assert!(out.contains("FAILED 4 : expected 2 outputs got 1"));
+        assert!(out.contains("FAILED 5 : invalid field element \"aéb0\""));
+    }
+
+    #[test]
+    fn byte_inputs() {
+        let one = Fr::from(1u64).into_bigint().to_bytes_le();
+        assert_eq!(decode_hex("0x0102").unwrap(), vec![1, 2]);
+        assert!(decode_hex("012").is_err());
+        assert_eq!(
+            decode_hex("aéb0"),
+            Err(MiMCError::InvalidNumber("aéb0".to_string()))
+        );
+        assert_eq!(
+            hash::<Fr>(
+                "mimc-7-91-bn254",
+                Mode::Permute,
+                None,
+                &Number::Text("0".into()),
+                1,
+                &Inputs::Bytes(one)
+            )
+            .unwrap(),
+            hash::<Fr>(
+                "mimc-7-91-bn254",
+                Mode::Permute,
+                None,
+                &Number::Text("0x0".into()),
+                1,
+                &Inputs::Numbers(vec![Number::Text("1".into())])
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn merkle_proofs() {
+        let args =
+            merkle_args("--preset mimc-7-91-bn254 --depth 3 --encoding hex --proof 1 --proof 6");
+        let (root, proofs) = merkle::<Fr>(&args, b"1\n0x2\n\n3\n").unwrap();
+        assert_eq!(proofs.len(), 2);
+
+        let json = serde_json::to_string(&proofs).unwrap();
+        let mimc: DynMiMC<Fr> = params::by_name("mimc-7-91-bn254").unwrap();
+        for (document, leaf) in serde_json::from_str::<Vec<MerkleProofDocument>>(&json)
+            .unwrap()
+            .iter()
+            .zip([2u64, 0])
+        {
+            assert_eq!(document.preset_id.as_deref(), Some("mimc-7-91-bn254"));
+            assert_eq!(document.permutation_type, Some(PermutationType::NonFeistel));
+            assert_eq!(document.root, root);
+            let (proof, root) = document.to_proof::<Fr>().unwrap();
+            assert_eq!(proof.leaf, Fr::from(leaf));
+            assert!(proof.verify_dyn(&mimc, root));
+        }
+
+        // The same leaves as binary, and a keyed feistel tree with another root
+        let binary: Vec<u8> = [1u64, 2, 3]
+            .iter()
+            .flat_map(|leaf| Fr::from(*leaf).into_bigint().to_bytes_le())
+            .collect();
+        let args =
+            merkle_args("--preset mimc-7-91-bn254 --depth 3 --encoding hex --leaf-format binary");
+        assert_eq!(merkle::<Fr>(&args, &binary).unwrap().0, root);
+        assert!(merkle::<Fr>(&args, &binary[1..]).is_err());
+
+        let args = merkle_args(
+            "--preset mimc-7-91-bn254 --depth 3 --permutation feistel --key 5 --proof 0",
+        );
+        let (keyed_root, proofs) = merkle::<Fr>(&args, b"1\n2\n3").unwrap();
+        assert_ne!(keyed_root, root);
+        let mimc = dyn_mimc::<Fr>(
+            "mimc-7-91-bn254",
+            Some(PermutationType::Feistel),
+            &Number::Text("5".into()),
+            1,
+        )
+        .unwrap();
+        let (proof, root) = proofs[0].to_proof::<Fr>().unwrap();
+        assert!(proof.verify_dyn(&mimc, root));
+
+        assert!(merkle::<Fr>(
+            &merkle_args("--preset mimc-7-91-bn254 --depth 1"),
+            b"1\n2\n3"
+        )
+        .is_err());
+        assert!(merkle::<Fr>(
+            &merkle_args("--preset mimc-7-91-bn254 --depth 1 --proof 2"),
+            b"1"
+        )
+        .is_err());
+        assert!(Cli::try_parse_from(
+            "mimc merkle --preset mimc-7-91-bn254 --depth 33".split_whitespace()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Sources for new presets: Rust modules in the style of `src/params`, and circom
+//! templates computing the same permutation. Used by the `mimc-paramgen` binary.
+
+use std::fmt::Write;
+
+use ark_ff::PrimeField;
+
+use crate::{exp_chain, PermutationType};
+
+/// Registry name such as `mimc-7-91-bn254`, see [`crate::params::by_name`]
+pub fn preset_name(field: &str, exponent: usize, rounds: usize) -> String {
+    format!("mimc-{exponent}-{rounds}-{field}")
+}
+
+/// A module for `src/params`, with round keys over `ark_<field>::Fr` where `field`
+/// is the curve name, such as `bn254` or `bls12-381`
+pub fn rust_module<F: PrimeField>(
+    field: &str,
+    permutation_type: PermutationType,
+    exponent: usize,
+    round_keys: &[F],
+) -> String {
+    let name = preset_name(field, exponent, round_keys.len());
+    let snake = name.replace('-', "_");
+    let params = format!("{}_PARAMS", snake.to_uppercase());
+    let table = format!("{}_ROUND_KEYS", snake.to_uppercase());
+
+    let mut module = format!(
+        "use ark_{}::Fr;
+use ark_ff::MontFp;
+
+use crate::{{params::MiMCPreset, MiMC, MiMCParameters, PermutationType}};
+
+#[derive(Debug, Clone, Default)]
+pub struct {params};
+
+impl MiMCParameters for {params} {{
+    const ROUNDS: usize = {};
+    const EXPONENT: usize = {exponent};
+    const PRESET_ID: Option<&'static str> = Some(<Self as MiMCPreset>::NAME);
+}}
+
+pub const {table}: [Fr; {params}::ROUNDS] = [
+",
+        field.replace('-', "_"),
+        round_keys.len(),
+    );
+    for c in round_keys {
+        writeln!(module, "    MontFp!(\"{}\"),", c.into_bigint()).unwrap();
+    }
+    write!(
+        module,
+        "];
+
+impl MiMCPreset for {params} {{
+    type Field = Fr;
+    const NAME: &'static str = \"{name}\";
+    const PERMUTATION_TYPE: PermutationType = PermutationType::{permutation_type:?};
+
+    fn round_keys() -> &'static [Fr] {{
+        &{table}
+    }}
+}}
+
+impl MiMC<Fr, {params}> {{
+    /// Key zero and one output with the bundled round keys, without parsing or validation
+    pub fn {snake}() -> Self {{
+        Self::from_preset(&{table})
+    }}
+}}
+"
+    )
+    .unwrap();
+    module
+}
+
+/// A circom template named `template` computing [`crate::MiMC`]'s rounds: with
+/// inputs `xL_in`, `xR_in`, `k` and outputs `xL_out`, `xR_out` like circomlib's
+/// `MiMCFeistel` for feistel, and with inputs `x_in`, `k` and output `out` like
+/// circomlib's `MiMC7` otherwise. `x^exponent` uses the same multiplication chain
+/// as [`crate::constraints`].
+pub fn circom_template<F: PrimeField>(
+    template: &str,
+    permutation_type: PermutationType,
+    exponent: usize,
+    round_keys: &[F],
+) -> String {
+    let rounds = round_keys.len();
+    let mut chain = vec![];
+    let out = exp_chain(&"t".to_string(), exponent, |a, b| {
+        let signal = format!("p[i][{}]", chain.len());
+        chain.push(format!("        {signal} <== {a} * {b};"));
+        signal
+    });
+    let muls = chain.len();
+
+    let (signals, first, next, outputs) = match permutation_type {
+        PermutationType::Feistel => (
+            "    signal input xL_in;
+    signal input xR_in;
+    signal input k;
+    signal output xL_out;
+    signal output xR_out;
+",
+            "k + xL_in",
+            "k + xL[i-1] + c[i]",
+            format!(
+                "        if (i < {last}) {{
+            xL[i] <== ((i == 0) ? xR_in : xR[i-1]) + {out};
+            xR[i] <== (i == 0) ? xL_in : xL[i-1];
+        }} else {{
+            xR_out <== xR[i-1] + {out};
+            xL_out <== xL[i-1];
+        }}
+",
+                last = rounds - 1
+            ),
+        ),
+        PermutationType::NonFeistel => (
+            "    signal input x_in;
+    signal input k;
+    signal output out;
+",
+            "k + x_in",
+            "k + r[i-1] + c[i]",
+            format!("        r[i] <== {out};\n"),
+        ),
+    };
+    let state = match permutation_type {
+        PermutationType::Feistel => format!("    signal xL[{0}];\n    signal xR[{0}];\n", rounds - 1),
+        PermutationType::NonFeistel => format!("    signal r[{rounds}];\n"),
+    };
+    let output = match permutation_type {
+        PermutationType::Feistel => String::new(),
+        PermutationType::NonFeistel => format!("\n    out <== r[{}] + k;\n", rounds - 1),
+    };
+
+    let mut source = format!(
+        "pragma circom 2.0.0;
+
+template {template}() {{
+{signals}
+    var c[{rounds}] = [
+"
+    );
+    for (i, c) in round_keys.iter().enumerate() {
+        let separator = if i + 1 < rounds { "," } else { "" };
+        writeln!(source, "        {}{separator}", c.into_bigint()).unwrap();
+    }
+    write!(
+        source,
+        "    ];
+
+    var t;
+    signal p[{rounds}][{muls}];
+{state}
+    for (var i = 0; i < {rounds}; i++) {{
+        t = (i == 0) ? {first} : {next};
+{}
+{outputs}    }}
+{output}}}
+",
+        chain.join("\n")
+    )
+    .unwrap();
+    source
+}
+
+#[cfg(all(test, feature = "paramgen"))]
+mod tests {
+    use super::{circom_template, preset_name, rust_module};
+    use crate::{paramgen::generate_default_round_keys, PermutationType};
+
+    #[test]
+    fn regenerates_bundled_modules() {
+        let (_, round_keys) =
+            generate_default_round_keys::<ark_bn254::Fr>(PermutationType::NonFeistel, 7).unwrap();
+        assert_eq!(
+            rust_module("bn254", PermutationType::NonFeistel, 7, &round_keys),
+            include_str!("params/mimc_7_91_bn254.rs")
+        );
+        let (_, round_keys) =
+            generate_default_round_keys::<ark_bls12_377::Fr>(PermutationType::Feistel, 5).unwrap();
+        assert_eq!(
+            rust_module("bls12-377", PermutationType::Feistel, 5, &round_keys),
+            include_str!("params/mimc_5_218_bls12_377.rs")
+        );
+    }
+
+    #[test]
+    fn circom() {
+        assert_eq!(preset_name("bn254", 5, 220), "mimc-5-220-bn254");
+        let (_, round_keys) =
+            generate_default_round_keys::<ark_bn254::Fr>(PermutationType::Feistel, 5).unwrap();
+        let source = circom_template("MiMCFeistel220", PermutationType::Feistel, 5, &round_keys);
+        assert!(source.contains("template MiMCFeistel220() {"));
+        assert!(source.contains("    var c[220] = [\n        0,\n"));
+        // t^5 as t^2, t^4 and t^4 * t
+        assert!(source.contains("signal p[220][3];"));
+        assert!(source.contains("p[i][2] <== p[i][1] * t;"));
+        assert!(source.contains("xR_out <== xR[i-1] + p[i][2];"));
+
+        let source = circom_template("MiMC7", PermutationType::NonFeistel, 7, &round_keys[..91]);
+        assert!(source.contains("signal p[91][4];"));
+        assert!(source.contains("out <== r[90] + k;"));
+    }
+}
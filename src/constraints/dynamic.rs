@@ -0,0 +1,345 @@
+use std::borrow::Borrow;
+
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, AllocationMode, Boolean, EqGadget},
+    R1CSVar,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::{validate_parameters, DynMiMC, MiMCError, MiMCParameters, PermutationType};
+
+use super::{constant_prefix, round_constants, sponge_feistel, sponge_non_feistel, MiMCVar};
+
+/// [`MiMCVar`] with the exponent, round count and permutation type held as values,
+/// the gadget of [`DynMiMC`]. Spends the same constraints as `MiMCVar<F, P>`
+/// with the same settings.
+#[derive(Debug, Clone)]
+pub struct DynMiMCVar<F: PrimeField> {
+    pub permutation_type: PermutationType,
+    pub num_outputs: usize,
+    /// Exponent, at least 2
+    exponent: usize,
+    /// Key, see [`DynMiMCVar::set_k`]
+    k: FpVar<F>,
+    /// Round keys, one per round, see [`DynMiMCVar::set_round_keys`]
+    round_keys: Vec<FpVar<F>>,
+    /// `k + round_keys[i]` as a single linear combination for feistel rounds, `k` for the first round
+    round_constants: Vec<FpVar<F>>,
+}
+
+impl<F: PrimeField> DynMiMCVar<F> {
+    /// # Panics
+    ///
+    /// If [`DynMiMCVar::try_new`] fails
+    pub fn new(
+        permutation_type: PermutationType,
+        exponent: usize,
+        num_outputs: usize,
+        k: FpVar<F>,
+        round_keys: Vec<FpVar<F>>,
+    ) -> Self {
+        Self::try_new(permutation_type, exponent, num_outputs, k, round_keys)
+            .expect("Invalid MiMC parameters")
+    }
+
+    /// Checks [`validate_parameters`] with one round per round key. Round key values
+    /// are not checked since they may be witnesses, see [`DynMiMC::try_new`].
+    pub fn try_new(
+        permutation_type: PermutationType,
+        exponent: usize,
+        num_outputs: usize,
+        k: FpVar<F>,
+        round_keys: Vec<FpVar<F>>,
+    ) -> Result<Self, MiMCError> {
+        validate_parameters::<F>(exponent, round_keys.len())?;
+        Self::try_new_unvalidated(permutation_type, exponent, num_outputs, k, round_keys)
+    }
+
+    /// Only checks that the exponent is at least 2 and there is at least one round,
+    /// as [`DynMiMC::try_new_unvalidated`]
+    pub fn try_new_unvalidated(
+        permutation_type: PermutationType,
+        exponent: usize,
+        num_outputs: usize,
+        k: FpVar<F>,
+        round_keys: Vec<FpVar<F>>,
+    ) -> Result<Self, MiMCError> {
+        if exponent < 2 {
+            return Err(MiMCError::InvalidExponent(exponent));
+        }
+        if round_keys.is_empty() {
+            return Err(MiMCError::TooFewRounds {
+                rounds: 0,
+                minimum: 1,
+            });
+        }
+        let round_constants = round_constants(&k, &round_keys);
+        Ok(Self {
+            permutation_type,
+            exponent,
+            num_outputs,
+            k,
+            round_keys,
+            round_constants,
+        })
+    }
+
+    /// Typed gadget with the same key and round keys for `permutation_type`
+    pub fn from_typed<P: MiMCParameters>(
+        mimc: &MiMCVar<F, P>,
+        permutation_type: PermutationType,
+    ) -> Self {
+        Self {
+            permutation_type,
+            exponent: P::EXPONENT,
+            num_outputs: mimc.num_outputs,
+            k: mimc.k.clone(),
+            round_keys: mimc.round_keys.clone(),
+            round_constants: mimc.round_constants.clone(),
+        }
+    }
+
+    /// Typed gadget with the same key and round keys, if the exponent and
+    /// round count match `P`. The permutation type is left to the caller.
+    pub fn to_typed<P: MiMCParameters>(&self) -> Result<MiMCVar<F, P>, MiMCError> {
+        if self.exponent != P::EXPONENT {
+            return Err(MiMCError::ExponentMismatch {
+                expected: P::EXPONENT,
+                actual: self.exponent,
+            });
+        }
+        MiMCVar::try_new(self.num_outputs, self.k.clone(), self.round_keys.clone())
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len()
+    }
+
+    pub fn exponent(&self) -> usize {
+        self.exponent
+    }
+
+    pub fn k(&self) -> &FpVar<F> {
+        &self.k
+    }
+
+    pub fn round_keys(&self) -> &[FpVar<F>] {
+        &self.round_keys
+    }
+
+    /// Replace the key and rebuild the round constants
+    pub fn set_k(&mut self, k: FpVar<F>) {
+        self.round_constants = round_constants(&k, &self.round_keys);
+        self.k = k;
+    }
+
+    /// Replace the round keys, which also sets the round count, and rebuild the
+    /// round constants. Only checks as [`DynMiMCVar::try_new_unvalidated`].
+    pub fn set_round_keys(&mut self, round_keys: Vec<FpVar<F>>) -> Result<(), MiMCError> {
+        *self = Self::try_new_unvalidated(
+            self.permutation_type,
+            self.exponent,
+            self.num_outputs,
+            self.k.clone(),
+            round_keys,
+        )?;
+        Ok(())
+    }
+
+    /// Native parameters when `k` and every round key are constants
+    fn constant_params(&self) -> Option<DynMiMC<F>> {
+        match self.k.is_constant() && self.round_keys.iter().all(|c| c.is_constant()) {
+            true => self.value().ok(),
+            false => None,
+        }
+    }
+
+    /// Permute `state` with [`DynMiMCVar::permutation_type`]. With constant `k` and
+    /// round keys, an all-constant `state` is evaluated natively and a constant
+    /// prefix of it is absorbed natively, as in [`MiMCVar`].
+    pub fn permute(&self, state: Vec<FpVar<F>>) -> Vec<FpVar<F>> {
+        let mut state = state;
+        let mut r = match self.permutation_type {
+            PermutationType::Feistel => FpVar::zero(),
+            PermutationType::NonFeistel => self.k.clone(),
+        };
+        let mut c = FpVar::zero();
+        if let Some(mimc) = self.constant_params() {
+            let prefix = constant_prefix(&state);
+            if prefix.len() == state.len() {
+                return mimc.permute(prefix).into_iter().map(FpVar::Constant).collect();
+            }
+            match self.permutation_type {
+                PermutationType::Feistel => {
+                    let (r_n, c_n) = mimc.absorb_feistel(&prefix);
+                    (r, c) = (FpVar::Constant(r_n), FpVar::Constant(c_n));
+                }
+                PermutationType::NonFeistel => {
+                    r = FpVar::Constant(mimc.absorb_non_feistel(&prefix));
+                }
+            }
+            state.drain(..prefix.len());
+        }
+        match self.permutation_type {
+            PermutationType::Feistel => {
+                sponge_feistel(&self.round_constants, self.exponent, self.num_outputs, (r, c), state)
+            }
+            PermutationType::NonFeistel => {
+                sponge_non_feistel(&self.round_keys, self.exponent, self.num_outputs, r, state)
+            }
+        }
+    }
+
+    /// Two-to-one compression, the first output of permuting `[left, right]`
+    pub fn compress(&self, left: FpVar<F>, right: FpVar<F>) -> FpVar<F> {
+        self.permute(vec![left, right])[0].clone()
+    }
+}
+
+impl<F: PrimeField> AllocVar<DynMiMC<F>, F> for DynMiMCVar<F> {
+    /// Allocate `k` and the round keys with `mode`. The exponent, round count and
+    /// permutation type are part of the circuit shape and are not allocated.
+    fn new_variable<T: Borrow<DynMiMC<F>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let mimc = f()?.borrow().clone();
+        let cs = cs.into().cs();
+        Ok(Self::try_new_unvalidated(
+            mimc.permutation_type,
+            mimc.exponent(),
+            mimc.num_outputs,
+            FpVar::new_variable(cs.clone(), || Ok(mimc.k()), mode)?,
+            Vec::<FpVar<F>>::new_variable(cs, || Ok(mimc.round_keys().to_vec()), mode)?,
+        )?)
+    }
+}
+
+impl<F: PrimeField> R1CSVar<F> for DynMiMCVar<F> {
+    type Value = DynMiMC<F>;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.k.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(DynMiMC::try_new_unvalidated(
+            self.permutation_type,
+            self.exponent,
+            self.num_outputs,
+            self.k.value()?,
+            self.round_keys.value()?,
+        )?)
+    }
+}
+
+impl<F: PrimeField> EqGadget<F> for DynMiMCVar<F> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        self.k
+            .is_eq(&other.k)?
+            .and(&self.round_keys.is_eq(&other.round_keys)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use ark_bls12_377::Fr;
+    use ark_r1cs_std::{
+        fields::fp::FpVar,
+        prelude::{AllocVar, AllocationMode},
+        R1CSVar,
+    };
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{constraints::MiMCVar, MiMC, MiMCError, MiMCParameters, PermutationType};
+
+    use super::DynMiMCVar;
+
+    #[derive(Clone, Default)]
+    struct MiMCMock;
+
+    impl MiMCParameters for MiMCMock {
+        const ROUNDS: usize = 5;
+        const EXPONENT: usize = 7;
+    }
+
+    #[test]
+    fn matches_typed() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mut mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        mimc.num_outputs = 2;
+        let inputs = (0..3).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+        for permutation_type in [PermutationType::Feistel, PermutationType::NonFeistel] {
+            let typed_cs = ConstraintSystem::<Fr>::new_ref();
+            let typed = MiMCVar::<_, MiMCMock>::new_witness(typed_cs.clone(), || Ok(&mimc))?;
+            let typed_inputs = Vec::<FpVar<Fr>>::new_witness(typed_cs.clone(), || Ok(inputs.clone()))?;
+            let typed_outputs = match permutation_type {
+                PermutationType::Feistel => typed.permute_feistel(typed_inputs),
+                PermutationType::NonFeistel => typed.permute_non_feistel(typed_inputs),
+            };
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let dyn_mimc = mimc.to_dyn(permutation_type);
+            let mimc_var = DynMiMCVar::new_variable(cs.clone(), || Ok(&dyn_mimc), AllocationMode::Witness)?;
+            let input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs.clone()))?;
+            let outputs = mimc_var.permute(input_vars);
+
+            assert_eq!(outputs.value()?, dyn_mimc.permute(inputs.clone()));
+            assert_eq!(outputs.value()?, typed_outputs.value()?);
+            assert_eq!(cs.num_constraints(), typed_cs.num_constraints());
+            assert!(cs.is_satisfied()?);
+
+            assert_eq!(mimc_var.value()?, dyn_mimc);
+            assert_eq!(mimc_var.to_typed::<MiMCMock>()?.value()?, mimc);
+            let from_typed = DynMiMCVar::from_typed(&typed, permutation_type);
+            assert_eq!(from_typed.value()?, dyn_mimc);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn try_new_validates() -> Result<(), Box<dyn Error>> {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let k = FpVar::new_witness(cs.clone(), || Ok(Fr::from(1)))?;
+        let round_keys = Vec::<FpVar<Fr>>::new_witness(cs, || Ok(vec![Fr::from(0); 5]))?;
+        let non_feistel = PermutationType::NonFeistel;
+
+        assert_eq!(
+            DynMiMCVar::try_new(non_feistel, 11, 1, k.clone(), round_keys.clone()).err(),
+            Some(MiMCError::TooFewRounds {
+                rounds: 5,
+                minimum: 73
+            })
+        );
+        assert_eq!(
+            DynMiMCVar::try_new_unvalidated(non_feistel, 1, 1, k.clone(), round_keys.clone()).err(),
+            Some(MiMCError::InvalidExponent(1))
+        );
+        assert!(DynMiMCVar::try_new_unvalidated(non_feistel, 11, 1, k, round_keys).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn constant_folding() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng).to_dyn(PermutationType::NonFeistel);
+        let inputs = (0..3).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let mimc_var = DynMiMCVar::new_constant(cs.clone(), &mimc)?;
+        let mut input_vars = Vec::<FpVar<Fr>>::new_constant(cs.clone(), &inputs[..2])?;
+        input_vars.push(FpVar::new_witness(cs.clone(), || Ok(inputs[2]))?);
+        let outputs = mimc_var.permute(input_vars);
+
+        assert_eq!(outputs.value()?, mimc.permute(inputs));
+        assert_eq!(cs.num_constraints(), MiMCMock::ROUNDS * 4);
+        Ok(())
+    }
+}
@@ -1,123 +1,271 @@
 use std::marker::PhantomData;
 
 use ark_ff::PrimeField;
-use ark_r1cs_std::{fields::fp::FpVar, prelude::FieldVar};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::FieldVar, R1CSVar};
 use ark_std::vec::Vec;
-use crate::MiMCParameters;
+use crate::{exp_chain, MiMC, MiMCError, MiMCParameters};
 
+mod dynamic;
 mod traits;
-// pub use traits::*;
+mod witness;
+
+pub use dynamic::DynMiMCVar;
+pub use traits::*;
+
+/// Number of constraints spent on `t^exponent` in one round, the length of the
+/// square-and-multiply chain: `floor(log2 e)` squarings and `popcount(e) - 1` multiplications.
+/// This is 2 for x^3, 3 for x^5 and 4 for x^7, while round keys and key additions
+/// are linear and free.
+///
+/// A feistel or non-feistel call spends `P::ROUNDS * constraints_per_round(P::EXPONENT)`,
+/// and a permutation makes one call per absorbed element and per extra output.
+pub const fn constraints_per_round(exponent: usize) -> usize {
+    (usize::BITS - 1 - exponent.leading_zeros()) as usize + exponent.count_ones() as usize - 1
+}
 
 #[derive(Debug, Clone)]
 pub struct MiMCVar<F: PrimeField, P: MiMCParameters> {
     pub num_outputs: usize,
-    pub k: FpVar<F>,
-    pub round_keys: Vec<FpVar<F>>,
+    /// Key, see [`MiMCVar::set_k`]
+    k: FpVar<F>,
+    /// Round keys, one per round, see [`MiMCVar::set_round_keys`]
+    round_keys: Vec<FpVar<F>>,
+    /// `k + round_keys[i]` as a single linear combination for feistel rounds, `k` for the first round
+    round_constants: Vec<FpVar<F>>,
     params: PhantomData<P>,
 }
 
 impl<F: PrimeField, P: MiMCParameters> MiMCVar<F, P> {
+    /// # Panics
+    ///
+    /// If `round_keys` is not `P::ROUNDS` long, see [`MiMCVar::try_new`]
     pub fn new(num_outputs: usize, k: FpVar<F>, round_keys: Vec<FpVar<F>>) -> Self {
-        assert_eq!(round_keys.len(), P::ROUNDS, "Invalid round keys length");
-        Self {
+        Self::try_new(num_outputs, k, round_keys).expect("Invalid round keys length")
+    }
+
+    pub fn try_new(
+        num_outputs: usize,
+        k: FpVar<F>,
+        round_keys: Vec<FpVar<F>>,
+    ) -> Result<Self, MiMCError> {
+        if round_keys.len() != P::ROUNDS {
+            return Err(MiMCError::InvalidRoundKeysLength {
+                expected: P::ROUNDS,
+                actual: round_keys.len(),
+            });
+        }
+        let round_constants = round_constants(&k, &round_keys);
+        Ok(Self {
             num_outputs,
             params: PhantomData,
             k,
             round_keys,
-        }
+            round_constants,
+        })
+    }
+
+    pub fn k(&self) -> &FpVar<F> {
+        &self.k
+    }
+
+    pub fn round_keys(&self) -> &[FpVar<F>] {
+        &self.round_keys
+    }
+
+    /// Replace the key and rebuild the round constants
+    pub fn set_k(&mut self, k: FpVar<F>) {
+        self.round_constants = round_constants(&k, &self.round_keys);
+        self.k = k;
+    }
+
+    /// Replace the round keys and rebuild the round constants.
+    /// Only checks the length, as [`MiMCVar::try_new`].
+    pub fn set_round_keys(&mut self, round_keys: Vec<FpVar<F>>) -> Result<(), MiMCError> {
+        *self = Self::try_new(self.num_outputs, self.k.clone(), round_keys)?;
+        Ok(())
     }
 }
 
 impl<F: PrimeField, P: MiMCParameters> MiMCVar<F, P> {
+    /// Native parameters when `k` and every round key are constants
+    fn constant_params(&self) -> Option<MiMC<F, P>> {
+        match self.k.is_constant() && self.round_keys.iter().all(|c| c.is_constant()) {
+            true => self.value().ok(),
+            false => None,
+        }
+    }
+
+    /// MiMC 2n/n x^exp permute. With constant `k` and round keys, an all-constant
+    /// `state` is evaluated natively and a constant prefix of it is absorbed natively.
     pub fn permute_feistel(&self, state: Vec<FpVar<F>>) -> Vec<FpVar<F>> {
         let mut r = FpVar::zero();
         let mut c = FpVar::zero();
-        for s in state.into_iter() {
-            r = &r + &s;
-            (r, c) = self.feistel(r, c);
-        }
-        let mut outputs = vec![r.clone()];
-        match self.num_outputs {
-            0 | 1 => outputs,
-            _ => {
-                for _ in 1..self.num_outputs {
-                    (r, c) = self.feistel(r.clone(), c);
-                    outputs.push(r.clone());
-                }
-                outputs
+        let mut state = state;
+        if let Some(mimc) = self.constant_params() {
+            let prefix = constant_prefix(&state);
+            if prefix.len() == state.len() {
+                return mimc.permute_feistel(prefix).into_iter().map(FpVar::Constant).collect();
             }
+            let (r_n, c_n) = mimc.absorb_feistel(&prefix);
+            (r, c) = (FpVar::Constant(r_n), FpVar::Constant(c_n));
+            state.drain(..prefix.len());
         }
+        sponge_feistel(&self.round_constants, P::EXPONENT, self.num_outputs, (r, c), state)
     }
 
-    fn feistel(&self, left: FpVar<F>, right: FpVar<F>) -> (FpVar<F>, FpVar<F>) {
-        let mut x_l = left;
-        let mut x_r = right;
-        for i in 0..P::ROUNDS {
-            let t = match i == 0 {
-                true => &self.k + &x_l,
-                false => &self.k + &x_l + &self.round_keys[i],
-            };
-            let mut tn = FpVar::one();
-            (0..P::EXPONENT).for_each(|_| tn = &tn * &t);
-            (x_l, x_r) = match i < P::ROUNDS - 1 {
-                true => (&x_r + &tn, x_l),
-                false => (x_l, &x_r + &tn),
-            };
-        }
-        (x_l, x_r)
-    }
-
+    /// MiMC n/n x^exp permute. With constant `k` and round keys, an all-constant
+    /// `state` is evaluated natively and a constant prefix of it is absorbed natively.
     pub fn permute_non_feistel(&self, state: Vec<FpVar<F>>) -> Vec<FpVar<F>> {
         let mut r = self.k.clone();
-        for s in state.into_iter() {
-            r = &r + &s + &self.non_feistel(&s, &r);
+        let mut state = state;
+        if let Some(mimc) = self.constant_params() {
+            let prefix = constant_prefix(&state);
+            if prefix.len() == state.len() {
+                return mimc.permute_non_feistel(prefix).into_iter().map(FpVar::Constant).collect();
+            }
+            r = FpVar::Constant(mimc.absorb_non_feistel(&prefix));
+            state.drain(..prefix.len());
         }
-        let mut outputs = vec![r.clone()];
-        match self.num_outputs {
-            0 | 1 => outputs,
-            _ => {
-                for _ in 1..self.num_outputs {
-                    r = &r + &self.non_feistel(&r, &r);
-                    outputs.push(r.clone());
-                }
-                outputs
+        sponge_non_feistel(&self.round_keys, P::EXPONENT, self.num_outputs, r, state)
+    }
+}
+
+/// Leading constant elements of `state`, which can be absorbed natively
+/// when the parameters are constant
+fn constant_prefix<F: PrimeField>(state: &[FpVar<F>]) -> Vec<F> {
+    state
+        .iter()
+        .map_while(|s| match s {
+            FpVar::Constant(c) => Some(*c),
+            FpVar::Var(_) => None,
+        })
+        .collect()
+}
+
+/// `k + round_keys[i]` as a single linear combination, `k` for the first round
+fn round_constants<F: PrimeField>(k: &FpVar<F>, round_keys: &[FpVar<F>]) -> Vec<FpVar<F>> {
+    round_keys
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match i == 0 {
+            true => k.clone(),
+            false => k + c,
+        })
+        .collect()
+}
+
+/// `t^exponent` with [`constraints_per_round`] multiplication constraints
+fn pow<F: PrimeField>(t: &FpVar<F>, exponent: usize) -> FpVar<F> {
+    exp_chain(t, exponent, |a, b| a * b)
+}
+
+/// Feistel sponge from the absorbed state `(r, c)`, shared by [`MiMCVar`] and [`DynMiMCVar`]
+fn sponge_feistel<F: PrimeField>(
+    round_constants: &[FpVar<F>],
+    exponent: usize,
+    num_outputs: usize,
+    (mut r, mut c): (FpVar<F>, FpVar<F>),
+    state: Vec<FpVar<F>>,
+) -> Vec<FpVar<F>> {
+    for s in state.into_iter() {
+        r = &r + &s;
+        (r, c) = feistel(round_constants, exponent, r, c);
+    }
+    let mut outputs = vec![r.clone()];
+    match num_outputs {
+        0 | 1 => outputs,
+        _ => {
+            for _ in 1..num_outputs {
+                (r, c) = feistel(round_constants, exponent, r.clone(), c);
+                outputs.push(r.clone());
             }
+            outputs
         }
     }
+}
 
-    fn non_feistel(&self, x: &FpVar<F>, k: &FpVar<F>) -> FpVar<F> {
-        let mut r = FpVar::zero();
-        for i in 0..P::ROUNDS {
-            let t = match i == 0 {
-                true => k + x,
-                false => k + r + &self.round_keys[i],
-            };
-            let mut tn = FpVar::one();
-            (0..P::EXPONENT).for_each(|_| tn = &tn * &t);
-            r = tn;
+fn feistel<F: PrimeField>(
+    round_constants: &[FpVar<F>],
+    exponent: usize,
+    left: FpVar<F>,
+    right: FpVar<F>,
+) -> (FpVar<F>, FpVar<F>) {
+    let rounds = round_constants.len();
+    let mut x_l = left;
+    let mut x_r = right;
+    for (i, round_constant) in round_constants.iter().enumerate() {
+        let tn = pow(&(&x_l + round_constant), exponent);
+        (x_l, x_r) = match i < rounds - 1 {
+            true => (&x_r + &tn, x_l),
+            false => (x_l, &x_r + &tn),
+        };
+    }
+    (x_l, x_r)
+}
+
+/// Non-feistel sponge from the absorbed state `r`, shared by [`MiMCVar`] and [`DynMiMCVar`]
+fn sponge_non_feistel<F: PrimeField>(
+    round_keys: &[FpVar<F>],
+    exponent: usize,
+    num_outputs: usize,
+    mut r: FpVar<F>,
+    state: Vec<FpVar<F>>,
+) -> Vec<FpVar<F>> {
+    for s in state.into_iter() {
+        r = &r + &s + &non_feistel(round_keys, exponent, &s, &r);
+    }
+    let mut outputs = vec![r.clone()];
+    match num_outputs {
+        0 | 1 => outputs,
+        _ => {
+            for _ in 1..num_outputs {
+                r = &r + &non_feistel(round_keys, exponent, &r, &r);
+                outputs.push(r.clone());
+            }
+            outputs
         }
-        r + k
     }
 }
 
+fn non_feistel<F: PrimeField>(
+    round_keys: &[FpVar<F>],
+    exponent: usize,
+    x: &FpVar<F>,
+    k: &FpVar<F>,
+) -> FpVar<F> {
+    let mut r = FpVar::zero();
+    for (i, round_key) in round_keys.iter().enumerate() {
+        let t = match i == 0 {
+            true => k + x,
+            false => k + r + round_key,
+        };
+        r = pow(&t, exponent);
+    }
+    r + k
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
 
     use ark_bls12_377::Fr;
-    use ark_crypto_primitives::crh::{constraints::TwoToOneCRHSchemeGadget, TwoToOneCRHScheme, CRHScheme, poseidon::{constraints::TwoToOneCRHGadget, TwoToOneCRH}};
+    use ark_crypto_primitives::crh::{constraints::TwoToOneCRHSchemeGadget, TwoToOneCRHScheme};
     // use ark_ff::to_bytes;
     use ark_r1cs_std::{
         fields::fp::FpVar,
-        prelude::{AllocVar, EqGadget, FieldVar},
+        prelude::{AllocVar, AllocationMode, EqGadget, FieldVar},
+        uint8::UInt8,
         R1CSVar, ToBytesGadget,
     };
     use ark_relations::r1cs::ConstraintSystem;
     use ark_std::test_rng;
 
-    use crate::constraints::traits::{MiMCNonFeistelCRHSchemeGadget, MiMCNonFeistelCRH, MiMCParameters};
+    use crate::{
+        constraints::traits::MiMCNonFeistelCRHSchemeGadget, MiMC, MiMCNonFeistelCRH,
+        MiMCParameters, PermutationType,
+    };
 
-    use super::MiMCVar;
+    use super::{constraints_per_round, MiMCVar};
 
     #[derive(Clone, Default)]
     struct MiMCMock;
@@ -127,6 +275,199 @@ mod tests {
         const EXPONENT: usize = 5;
     }
 
+    #[derive(Clone, Default)]
+    struct MiMCMock7;
+
+    impl MiMCParameters for MiMCMock7 {
+        const ROUNDS: usize = 5;
+        const EXPONENT: usize = 7;
+    }
+
+    #[derive(Clone, Default)]
+    struct MiMCMock11;
+
+    impl MiMCParameters for MiMCMock11 {
+        const ROUNDS: usize = 5;
+        const EXPONENT: usize = 11;
+    }
+
+    fn check_constraint_count<P: MiMCParameters>() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, P>::rand_unvalidated(rng);
+        let inputs = vec![Fr::from(20), Fr::from(200)];
+
+        for feistel in [true, false] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs.clone()))?;
+            let k_var = FpVar::new_witness(cs.clone(), || Ok(mimc.k))?;
+            let round_keys = Vec::<FpVar<Fr>>::new_constant(cs.clone(), mimc.round_keys.clone())?;
+            let mimc_var = MiMCVar::<_, P>::new(1, k_var, round_keys);
+
+            let (hashed, hashed_var) = match feistel {
+                true => (
+                    mimc.permute_feistel(inputs.clone()),
+                    mimc_var.permute_feistel(input_vars),
+                ),
+                false => (
+                    mimc.permute_non_feistel(inputs.clone()),
+                    mimc_var.permute_non_feistel(input_vars),
+                ),
+            };
+
+            assert_eq!(hashed_var.value()?, hashed);
+            assert_eq!(
+                cs.num_constraints(),
+                inputs.len() * P::ROUNDS * constraints_per_round(P::EXPONENT)
+            );
+            assert!(cs.is_satisfied()?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn allocation_modes() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        let inputs = vec![Fr::from(20), Fr::from(200)];
+
+        for (mode, constant_round_keys) in [
+            (AllocationMode::Witness, false),
+            (AllocationMode::Input, false),
+            (AllocationMode::Witness, true),
+        ] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let mimc_var = match constant_round_keys {
+                true => MiMCVar::new_with_constant_round_keys(cs.clone(), || Ok(&mimc), mode)?,
+                false => MiMCVar::new_variable(cs.clone(), || Ok(&mimc), mode)?,
+            };
+            let allocated = match constant_round_keys {
+                true => 1,
+                false => 1 + MiMCMock::ROUNDS,
+            };
+            match mode {
+                AllocationMode::Input => assert_eq!(cs.num_instance_variables(), 1 + allocated),
+                _ => assert_eq!(cs.num_witness_variables(), allocated),
+            }
+            assert_eq!(mimc_var.value()?, mimc);
+
+            let input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs.clone()))?;
+            assert_eq!(
+                mimc_var.permute_feistel(input_vars.clone()).value()?,
+                mimc.permute_feistel(inputs.clone())
+            );
+            assert_eq!(
+                mimc_var.permute_non_feistel(input_vars).value()?,
+                mimc.permute_non_feistel(inputs.clone())
+            );
+            assert!(cs.is_satisfied()?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn setters_rebuild_round_constants() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        let mut changed = mimc.clone();
+        changed.set_k(mimc.k() + Fr::from(1));
+        let inputs = vec![Fr::from(20), Fr::from(200)];
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let mut mimc_var = MiMCVar::new_witness(cs.clone(), || Ok(&mimc))?;
+        mimc_var.set_k(FpVar::new_witness(cs.clone(), || Ok(changed.k()))?);
+        let input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs.clone()))?;
+        assert_eq!(
+            mimc_var.permute_feistel(input_vars.clone()).value()?,
+            changed.permute_feistel(inputs.clone())
+        );
+        assert_eq!(
+            mimc_var.permute_non_feistel(input_vars).value()?,
+            changed.permute_non_feistel(inputs)
+        );
+        assert!(mimc_var.set_round_keys(vec![]).is_err());
+        assert!(cs.is_satisfied()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors_instead_of_panics() -> Result<(), Box<dyn Error>> {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let k_var = FpVar::new_witness(cs.clone(), || Ok(Fr::from(1)))?;
+        assert!(MiMCVar::<_, MiMCMock>::try_new(1, k_var.clone(), vec![]).is_err());
+
+        let mimc_var = MiMCVar::<_, MiMCMock>::try_new(1, k_var, vec![FpVar::zero(); 5])?;
+        let left = witness_bytes(&cs, 2)?;
+        let right = witness_bytes(&cs, 1)?;
+        assert!(
+            <MiMCNonFeistelCRHSchemeGadget<_, MiMCMock> as TwoToOneCRHSchemeGadget<
+                MiMCNonFeistelCRH<_, _>,
+                _,
+            >>::evaluate(&mimc_var, &left, &right)
+            .is_err()
+        );
+
+        Ok(())
+    }
+
+    fn witness_bytes(
+        cs: &ark_relations::r1cs::ConstraintSystemRef<Fr>,
+        len: usize,
+    ) -> Result<Vec<UInt8<Fr>>, Box<dyn Error>> {
+        Ok(UInt8::new_witness_vec(cs.clone(), &vec![0u8; len])?)
+    }
+
+    #[test]
+    fn constant_folding() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        let inputs = vec![Fr::from(20), Fr::from(200), Fr::from(2000)];
+
+        for permutation_type in [PermutationType::Feistel, PermutationType::NonFeistel] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let mimc_var = MiMCVar::new_with_constant_round_keys(
+                cs.clone(),
+                || Ok(&mimc),
+                AllocationMode::Constant,
+            )?;
+            let permute = |state: Vec<FpVar<Fr>>| match permutation_type {
+                PermutationType::Feistel => mimc_var.permute_feistel(state),
+                PermutationType::NonFeistel => mimc_var.permute_non_feistel(state),
+            };
+            let expected = mimc.permute(permutation_type, inputs.clone());
+
+            let constant = permute(inputs.iter().map(|x| FpVar::Constant(*x)).collect());
+            assert!(constant.is_constant());
+            assert_eq!(constant.value()?, expected);
+            assert_eq!(cs.num_constraints(), 0);
+
+            let mut state = Vec::<FpVar<Fr>>::new_constant(cs.clone(), &inputs[..2])?;
+            state.push(FpVar::new_witness(cs.clone(), || Ok(inputs[2]))?);
+            assert_eq!(permute(state).value()?, expected);
+            assert_eq!(
+                cs.num_constraints(),
+                MiMCMock::ROUNDS * constraints_per_round(MiMCMock::EXPONENT)
+            );
+            assert!(cs.is_satisfied()?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn constraints_per_round_count() -> Result<(), Box<dyn Error>> {
+        assert_eq!(constraints_per_round(3), 2);
+        assert_eq!(constraints_per_round(5), 3);
+        assert_eq!(constraints_per_round(7), 4);
+        assert_eq!(constraints_per_round(11), 5);
+
+        check_constraint_count::<MiMCMock>()?;
+        check_constraint_count::<MiMCMock7>()?;
+        check_constraint_count::<MiMCMock11>()
+    }
+
     // #[test]
     // fn constraints_feistel() -> Result<(), Box<dyn Error>> {
     //     let rng = &mut test_rng();
@@ -159,21 +500,49 @@ mod tests {
     //     Ok(())
     // }
 
+    #[test]
+    fn compress_matches_native() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+
+        let (left, right) = (Fr::from(20), Fr::from(200));
+        let hashed =
+            <MiMCNonFeistelCRH<Fr, MiMCMock> as TwoToOneCRHScheme>::compress(&mimc, left, right)?;
+        assert_eq!(hashed, mimc.permute_non_feistel(vec![left, right])[0]);
+
+        let left_var = FpVar::new_witness(cs.clone(), || Ok(left))?;
+        let right_var = FpVar::new_witness(cs.clone(), || Ok(right))?;
+        let k_var = FpVar::new_input(cs.clone(), || Ok(mimc.k))?;
+        let round_keys = Vec::<FpVar<Fr>>::new_constant(cs.clone(), mimc.round_keys.clone())?;
+        let mimc_var = MiMCVar::<_, MiMCMock>::new(1, k_var, round_keys);
+        let hashed_var =
+            <MiMCNonFeistelCRHSchemeGadget<_, MiMCMock> as TwoToOneCRHSchemeGadget<
+                MiMCNonFeistelCRH<_, _>,
+                _,
+            >>::compress(&mimc_var, &left_var, &right_var)?;
+
+        assert_eq!(hashed_var.value()?, hashed);
+        assert!(cs.is_satisfied()?);
+
+        Ok(())
+    }
+
     #[test]
     fn constraints_non_feistel() -> Result<(), Box<dyn Error>> {
         use ark_serialize::CanonicalSerialize;
 
         let rng = &mut test_rng();
         let cs = ConstraintSystem::<Fr>::new_ref();
-        let mimc = <MiMCNonFeistelCRH<Fr, MiMCMock> as CRHScheme>::setup(rng)?;
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
 
         let x_l = Fr::from(20);
         let mut x_l_bytes = vec![];
-        x_l.serialize_compressed(&mut x_l_bytes);
+        x_l.serialize_compressed(&mut x_l_bytes)?;
         
         let x_r = Fr::from(200);
         let mut x_r_bytes = vec![];
-        x_r.serialize_compressed(&mut x_r_bytes);
+        x_r.serialize_compressed(&mut x_r_bytes)?;
         let hashed = <MiMCNonFeistelCRH<Fr, MiMCMock> as TwoToOneCRHScheme>::evaluate(
             &mimc,
             x_l_bytes,
@@ -1,14 +1,14 @@
-use std::marker::PhantomData;
+use std::{borrow::Borrow, marker::PhantomData};
 
 use ark_crypto_primitives::crh::{TwoToOneCRHSchemeGadget, CRHSchemeGadget};
 use ark_ff::PrimeField;
 use ark_r1cs_std::{
-    fields::{FieldVar, fp::FpVar}, prelude::{AllocVar, EqGadget}, uint8::UInt8, R1CSVar
+    fields::fp::FpVar, prelude::{AllocVar, AllocationMode, EqGadget}, uint8::UInt8, R1CSVar
 };
-use ark_relations::r1cs::SynthesisError;
+use ark_relations::r1cs::{Namespace, SynthesisError};
 
 use crate::{
-    utils::to_field_elements_r1cs, MiMC, MiMCNonFeistelCRH, MiMCParameters,
+    utils::to_field_elements_r1cs, MiMC, MiMCError, MiMCNonFeistelCRH, MiMCParameters,
 };
 
 use super::MiMCVar;
@@ -22,25 +22,46 @@ pub struct MiMCNonFeistelCRHSchemeGadget<F: PrimeField, P: MiMCParameters>(
     PhantomData<P>,
 );
 
-impl<F: PrimeField, P: MiMCParameters> AllocVar<MiMC<F, P>, F> for MiMCVar<F, P> {
-    fn new_variable<T: std::borrow::Borrow<MiMC<F, P>>>(
-        cs: impl Into<ark_relations::r1cs::Namespace<F>>,
-        f: impl FnOnce() -> Result<T, ark_relations::r1cs::SynthesisError>,
-        mode: ark_r1cs_std::prelude::AllocationMode,
-    ) -> Result<Self, ark_relations::r1cs::SynthesisError> {
+impl<F: PrimeField, P: MiMCParameters> MiMCVar<F, P> {
+    /// Allocate `k` with `mode` and the round keys as constants, which folds them into
+    /// linear combinations and keeps them out of the witness and public input.
+    /// Only sound when the round keys are fixed parameters of the circuit.
+    pub fn new_with_constant_round_keys<T: Borrow<MiMC<F, P>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        Self::new_with_round_keys_mode(cs, f, mode, AllocationMode::Constant)
+    }
+
+    fn new_with_round_keys_mode<T: Borrow<MiMC<F, P>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+        round_keys_mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
         let mimc = f()?.borrow().clone();
         let cs = cs.into().cs();
-        Ok(Self {
-            num_outputs: mimc.num_outputs,
-            params: PhantomData,
-            k: FpVar::new_variable(cs.clone(), || Ok(mimc.k), mode)?,
-            round_keys: mimc
-                .round_keys
+        Ok(Self::try_new(
+            mimc.num_outputs,
+            FpVar::new_variable(cs.clone(), || Ok(mimc.k), mode)?,
+            mimc.round_keys
                 .into_iter()
-                // .map(|e| -> Result<_, _> { FpVar::new_variable(cs.clone(), || Ok(e), mode) })
-                .map(|e| -> Result<_, _> { FpVar::new_variable(cs.clone(), || Ok(e), ark_r1cs_std::prelude::AllocationMode::Constant) })
+                .map(|e| -> Result<_, _> { FpVar::new_variable(cs.clone(), || Ok(e), round_keys_mode) })
                 .collect::<Result<Vec<_>, _>>()?,
-        })
+        )?)
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> AllocVar<MiMC<F, P>, F> for MiMCVar<F, P> {
+    /// Allocate `k` and the round keys with `mode`, see
+    /// [`MiMCVar::new_with_constant_round_keys`] for public fixed round keys
+    fn new_variable<T: Borrow<MiMC<F, P>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        Self::new_with_round_keys_mode(cs, f, mode, mode)
     }
 }
 
@@ -52,12 +73,11 @@ impl<F: PrimeField, P: MiMCParameters> R1CSVar<F> for MiMCVar<F, P> {
     }
 
     fn value(&self) -> Result<Self::Value, ark_relations::r1cs::SynthesisError> {
-        Ok(MiMC {
-            num_outputs: self.num_outputs,
-            k: self.k.value()?,
-            round_keys: self.round_keys.value()?,
-            params: PhantomData,
-        })
+        Ok(MiMC::try_new_unvalidated(
+            self.num_outputs,
+            self.k.value()?,
+            self.round_keys.value()?,
+        )?)
     }
 }
 
@@ -125,7 +145,7 @@ impl<F: PrimeField, P: MiMCParameters> CRHSchemeGadget<MiMCNonFeistelCRH<F, P>,
         input: &[ark_r1cs_std::uint8::UInt8<F>],
     ) -> Result<Self::OutputVar, ark_relations::r1cs::SynthesisError> {
         let fields: Vec<FpVar<F>> = to_field_elements_r1cs(input)?;
-        Ok(parameters.permute_non_feistel(fields)[0].clone())
+        Ok(parameters.permute_non_feistel_native_witness(fields)?[0].clone())
     }
 }
 
@@ -141,7 +161,13 @@ impl<F: PrimeField, P: MiMCParameters> TwoToOneCRHSchemeGadget<MiMCNonFeistelCRH
         left_input: &[ark_r1cs_std::uint8::UInt8<F>],
         right_input: &[ark_r1cs_std::uint8::UInt8<F>],
     ) -> Result<Self::OutputVar, ark_relations::r1cs::SynthesisError> {
-        assert_eq!(left_input.len(), right_input.len());
+        if left_input.len() != right_input.len() {
+            return Err(MiMCError::InputLengthMismatch {
+                left: left_input.len(),
+                right: right_input.len(),
+            }
+            .into());
+        }
         let chained: Vec<_> = left_input
             .iter()
             .chain(right_input.iter())
@@ -156,7 +182,9 @@ impl<F: PrimeField, P: MiMCParameters> TwoToOneCRHSchemeGadget<MiMCNonFeistelCRH
         left_input: &Self::OutputVar,
         right_input: &Self::OutputVar,
     ) -> Result<Self::OutputVar, SynthesisError> {
-        Ok(FpVar::<F>::zero())
+        Ok(parameters
+            .permute_non_feistel_native_witness(vec![left_input.clone(), right_input.clone()])?[0]
+            .clone())
     }
 
 }
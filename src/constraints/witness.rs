@@ -0,0 +1,331 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::{AllocatedFp, FpVar},
+    R1CSVar,
+};
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSystemRef, LinearCombination, SynthesisError, Variable},
+};
+
+use crate::{exp_chain, MiMCParameters, PermutationType};
+
+use super::{constant_prefix, constraints_per_round, MiMCVar};
+
+/// Terms a round value keeps inline before it is registered as a symbolic linear
+/// combination, which bounds the size of every constraint. Below it sums cost no
+/// lookups in the constraint system, where `FpVar` registers every one.
+const MAX_INLINE_TERMS: usize = 4;
+
+/// Round value tracked as a plain linear combination instead of an `FpVar`
+#[derive(Clone)]
+enum Term<F: PrimeField> {
+    Constant(F),
+    Lc(LinearCombination<F>),
+}
+
+impl<F: PrimeField> Term<F> {
+    fn new(var: &FpVar<F>) -> Self {
+        match var {
+            FpVar::Constant(c) => Term::Constant(*c),
+            FpVar::Var(v) => Term::Lc(lc!() + v.variable),
+        }
+    }
+}
+
+/// Emits the multiplication constraints of the round functions, taking every
+/// product from a native trace rather than evaluating it through `FpVar`
+struct WitnessBuilder<'a, F: PrimeField> {
+    cs: ConstraintSystemRef<F>,
+    trace: Option<&'a [F]>,
+    step: usize,
+}
+
+impl<'a, F: PrimeField> WitnessBuilder<'a, F> {
+    fn add(&self, a: &Term<F>, b: &Term<F>) -> Result<Term<F>, SynthesisError> {
+        match (a, b) {
+            (Term::Constant(a), Term::Constant(b)) => Ok(Term::Constant(*a + b)),
+            (Term::Constant(c), Term::Lc(lc)) | (Term::Lc(lc), Term::Constant(c)) => {
+                self.bounded(lc.clone() + (*c, Variable::One))
+            }
+            (Term::Lc(a), Term::Lc(b)) => self.bounded(a + b),
+        }
+    }
+
+    /// `lc` itself, or a symbolic variable standing for it once it is longer
+    /// than [`MAX_INLINE_TERMS`]
+    fn bounded(&self, lc: LinearCombination<F>) -> Result<Term<F>, SynthesisError> {
+        match lc.0.len() > MAX_INLINE_TERMS {
+            true => Ok(Term::Lc(lc!() + self.cs.new_lc(lc)?)),
+            false => Ok(Term::Lc(lc)),
+        }
+    }
+
+    /// Same constraint as `FpVar` multiplication: none when either side is constant,
+    /// otherwise one new witness `c` with `a * b = c`
+    fn mul(&mut self, a: &Term<F>, b: &Term<F>) -> Result<Term<F>, SynthesisError> {
+        let value = self.trace.map(|trace| trace[self.step]);
+        self.step += 1;
+        match (a, b) {
+            (Term::Constant(a), Term::Constant(b)) => Ok(Term::Constant(*a * b)),
+            (Term::Constant(c), Term::Lc(lc)) | (Term::Lc(lc), Term::Constant(c)) => {
+                Ok(Term::Lc(lc * *c))
+            }
+            (Term::Lc(a), Term::Lc(b)) => {
+                let product = self
+                    .cs
+                    .new_witness_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+                self.cs
+                    .enforce_constraint(a.clone(), b.clone(), lc!() + product)?;
+                Ok(Term::Lc(lc!() + product))
+            }
+        }
+    }
+
+    fn pow<P: MiMCParameters>(&mut self, t: Term<F>) -> Result<Term<F>, SynthesisError> {
+        exp_chain(&Ok(t), P::EXPONENT, |a, b| match (a, b) {
+            (Ok(a), Ok(b)) => self.mul(a, b),
+            (Err(e), _) | (_, Err(e)) => Err(*e),
+        })
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> MiMCVar<F, P> {
+    /// Same constraints as [`MiMCVar::permute_feistel`], with every round value
+    /// computed by the native [`crate::MiMC`] first and allocated directly as witnesses
+    pub fn permute_feistel_native_witness(
+        &self,
+        state: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        self.permute_native_witness(PermutationType::Feistel, state)
+    }
+
+    /// Same constraints as [`MiMCVar::permute_non_feistel`], with every round value
+    /// computed by the native [`crate::MiMC`] first and allocated directly as witnesses
+    pub fn permute_non_feistel_native_witness(
+        &self,
+        state: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        self.permute_native_witness(PermutationType::NonFeistel, state)
+    }
+
+    fn permute_native_witness(
+        &self,
+        permutation_type: PermutationType,
+        state: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let cs = state
+            .iter()
+            .chain(&self.round_keys)
+            .fold(self.k.cs(), |cs, v| cs.or(v.cs()));
+        // Same constant folding as the `FpVar` path, skipping the trace of the absorbed prefix
+        let mut initial = None;
+        let mut absorbed = 0;
+        if let Some(mimc) = self.constant_params() {
+            let prefix = constant_prefix(&state);
+            if prefix.len() == state.len() {
+                return Ok(mimc
+                    .permute(permutation_type, prefix)
+                    .into_iter()
+                    .map(FpVar::Constant)
+                    .collect());
+            }
+            absorbed = prefix.len();
+            initial = Some(match permutation_type {
+                PermutationType::Feistel => mimc.absorb_feistel(&prefix),
+                PermutationType::NonFeistel => (mimc.absorb_non_feistel(&prefix), F::zero()),
+            });
+        }
+        // Unavailable in setup mode, where witnesses are never evaluated
+        let native = match (self.value(), state.value()) {
+            (Ok(mimc), Ok(state)) => Some(mimc.permute_traced(permutation_type, state)),
+            _ => None,
+        };
+        let mut builder = WitnessBuilder {
+            cs: cs.clone(),
+            trace: native.as_ref().map(|(_, trace)| trace.as_slice()),
+            step: absorbed * P::ROUNDS * constraints_per_round(P::EXPONENT),
+        };
+
+        let state = state[absorbed..].iter().map(Term::new).collect::<Vec<_>>();
+        let outputs = match permutation_type {
+            PermutationType::Feistel => {
+                self.permute_feistel_terms(&mut builder, initial, state)?
+            }
+            PermutationType::NonFeistel => {
+                self.permute_non_feistel_terms(&mut builder, initial, state)?
+            }
+        };
+
+        outputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, output)| match output {
+                Term::Constant(c) => Ok(FpVar::Constant(c)),
+                Term::Lc(lc) => {
+                    let value = native.as_ref().map(|(outputs, _)| outputs[i]);
+                    let variable = cs.new_lc(lc)?;
+                    Ok(FpVar::Var(AllocatedFp::new(value, variable, cs.clone())))
+                }
+            })
+            .collect()
+    }
+
+    fn permute_feistel_terms(
+        &self,
+        builder: &mut WitnessBuilder<F>,
+        initial: Option<(F, F)>,
+        state: Vec<Term<F>>,
+    ) -> Result<Vec<Term<F>>, SynthesisError> {
+        let round_constants = self.round_constants.iter().map(Term::new).collect::<Vec<_>>();
+        let (r, c) = initial.unwrap_or((F::zero(), F::zero()));
+        let mut r = Term::Constant(r);
+        let mut c = Term::Constant(c);
+        for s in state.iter() {
+            r = builder.add(&r, s)?;
+            (r, c) = Self::feistel_terms(builder, &round_constants, r, c)?;
+        }
+        let mut outputs = vec![r.clone()];
+        for _ in 1..self.num_outputs {
+            (r, c) = Self::feistel_terms(builder, &round_constants, r, c)?;
+            outputs.push(r.clone());
+        }
+        Ok(outputs)
+    }
+
+    fn feistel_terms(
+        builder: &mut WitnessBuilder<F>,
+        round_constants: &[Term<F>],
+        left: Term<F>,
+        right: Term<F>,
+    ) -> Result<(Term<F>, Term<F>), SynthesisError> {
+        let mut x_l = left;
+        let mut x_r = right;
+        for (i, round_constant) in round_constants.iter().enumerate() {
+            let tn = builder.pow::<P>(builder.add(&x_l, round_constant)?)?;
+            (x_l, x_r) = match i < P::ROUNDS - 1 {
+                true => (builder.add(&x_r, &tn)?, x_l),
+                false => (x_l, builder.add(&x_r, &tn)?),
+            };
+        }
+        Ok((x_l, x_r))
+    }
+
+    fn permute_non_feistel_terms(
+        &self,
+        builder: &mut WitnessBuilder<F>,
+        initial: Option<(F, F)>,
+        state: Vec<Term<F>>,
+    ) -> Result<Vec<Term<F>>, SynthesisError> {
+        let round_keys = self.round_keys.iter().map(Term::new).collect::<Vec<_>>();
+        let mut r = match initial {
+            Some((r, _)) => Term::Constant(r),
+            None => Term::new(&self.k),
+        };
+        for s in state.iter() {
+            let t = Self::non_feistel_terms(builder, &round_keys, s, &r)?;
+            r = builder.add(&builder.add(&r, s)?, &t)?;
+        }
+        let mut outputs = vec![r.clone()];
+        for _ in 1..self.num_outputs {
+            let t = Self::non_feistel_terms(builder, &round_keys, &r, &r)?;
+            r = builder.add(&r, &t)?;
+            outputs.push(r.clone());
+        }
+        Ok(outputs)
+    }
+
+    fn non_feistel_terms(
+        builder: &mut WitnessBuilder<F>,
+        round_keys: &[Term<F>],
+        x: &Term<F>,
+        k: &Term<F>,
+    ) -> Result<Term<F>, SynthesisError> {
+        let mut r = Term::Constant(F::zero());
+        for (i, round_key) in round_keys.iter().enumerate() {
+            let t = match i == 0 {
+                true => builder.add(k, x)?,
+                false => builder.add(&builder.add(k, &r)?, round_key)?,
+            };
+            r = builder.pow::<P>(t)?;
+        }
+        builder.add(&r, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use ark_bls12_377::Fr;
+    use ark_r1cs_std::{fields::fp::FpVar, prelude::AllocVar, R1CSVar};
+    use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef, SynthesisMode};
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{MiMC, MiMCParameters, PermutationType};
+
+    use super::MiMCVar;
+
+    #[derive(Clone, Default)]
+    struct MiMCMock;
+
+    impl MiMCParameters for MiMCMock {
+        const ROUNDS: usize = 5;
+        const EXPONENT: usize = 7;
+    }
+
+    fn synthesize(
+        permutation_type: PermutationType,
+        native_witness: bool,
+        setup: bool,
+        constant_params: bool,
+    ) -> Result<ConstraintSystemRef<Fr>, Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mut mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        mimc.num_outputs = 2;
+        let inputs = vec![Fr::rand(rng), Fr::from(3), Fr::rand(rng)];
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        if setup {
+            cs.set_mode(SynthesisMode::Setup);
+        }
+        let mut input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs.clone()))?;
+        input_vars[1] = FpVar::Constant(inputs[1]);
+        let mimc_var = match constant_params {
+            true => {
+                input_vars[0] = FpVar::Constant(inputs[0]);
+                MiMCVar::new_constant(cs.clone(), mimc.clone())?
+            }
+            false => MiMCVar::new_witness(cs.clone(), || Ok(mimc.clone()))?,
+        };
+
+        let outputs = match (permutation_type, native_witness) {
+            (PermutationType::Feistel, true) => mimc_var.permute_feistel_native_witness(input_vars)?,
+            (PermutationType::Feistel, false) => mimc_var.permute_feistel(input_vars),
+            (PermutationType::NonFeistel, true) => {
+                mimc_var.permute_non_feistel_native_witness(input_vars)?
+            }
+            (PermutationType::NonFeistel, false) => mimc_var.permute_non_feistel(input_vars),
+        };
+        if !setup {
+            assert_eq!(outputs.value()?, mimc.permute(permutation_type, inputs));
+            assert!(cs.is_satisfied()?);
+        }
+        cs.finalize();
+        Ok(cs)
+    }
+
+    #[test]
+    fn native_witness_same_constraints() -> Result<(), Box<dyn Error>> {
+        for permutation_type in [PermutationType::Feistel, PermutationType::NonFeistel] {
+            for (setup, constant_params) in [(false, false), (true, false), (false, true)] {
+                let expected = synthesize(permutation_type, false, setup, constant_params)?;
+                let actual = synthesize(permutation_type, true, setup, constant_params)?;
+                assert_eq!(actual.num_constraints(), expected.num_constraints());
+                assert_eq!(actual.num_witness_variables(), expected.num_witness_variables());
+                assert_eq!(actual.to_matrices(), expected.to_matrices());
+            }
+        }
+        Ok(())
+    }
+}
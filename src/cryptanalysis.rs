@@ -0,0 +1,353 @@
+//! Reduced-round MiMC-n/n over toy fields, with the interpolation and GCD key
+//! recovery attacks of the MiMC paper. Both recover the key while `e^r < p` and
+//! fail from `ceil(log_e p)` rounds on, which is the bound [`crate::validate`]
+//! enforces. Only for fields below [`MAX_FIELD_SIZE`] and polynomials up to
+//! [`MAX_ATTACK_DEGREE`], use [`crate::security`] to estimate real parameters.
+// The `MontConfig` derive of ark-ff 0.4 implements traits inside a `const` block
+#![allow(non_local_definitions)]
+
+use ark_ff::{
+    fields::{Fp64, MontBackend, MontConfig},
+    Field, PrimeField,
+};
+
+use crate::{exp_chain, non_feistel_rounds, DynMiMC, MiMCError, PermutationType};
+
+/// Largest modulus the attacks run over
+pub const MAX_FIELD_SIZE: u64 = 1 << 20;
+
+/// Largest polynomial degree the attacks handle. Interpolation and the polynomial
+/// gcd take time quadratic in the degree.
+pub const MAX_ATTACK_DEGREE: u64 = 1 << 12;
+
+#[derive(MontConfig)]
+#[modulus = "101"]
+#[generator = "2"]
+pub struct F101Config;
+/// `p = 101`, where `x^3` needs 5 rounds
+pub type F101 = Fp64<MontBackend<F101Config, 1>>;
+
+#[derive(MontConfig)]
+#[modulus = "65537"]
+#[generator = "3"]
+pub struct F65537Config;
+/// `p = 2^16 + 1`, where `x^3` needs 11 rounds
+pub type F65537 = Fp64<MontBackend<F65537Config, 1>>;
+
+/// MiMC-n/n encryption of `x` under `mimc.k`, the non-feistel rounds that
+/// [`DynMiMC::permute`] absorbs with
+pub fn encrypt<F: PrimeField>(mimc: &DynMiMC<F>, x: F) -> F {
+    non_feistel_rounds(mimc.round_keys(), x, mimc.k(), &mut |t| {
+        exp_chain(&t, mimc.exponent(), |a, b| *a * b)
+    })
+}
+
+/// Recover the key of [`encrypt`] from chosen plaintexts. `E_k(x)` has degree
+/// `d = e^r` in `x` with `d k` as the coefficient of `x^(d - 1)`, so `d + 1`
+/// encryptions determine `k`. From `e^r >= p` on, even the whole codebook only
+/// gives the polynomial reduced mod `x^p - x` and the candidate is rejected.
+///
+/// `None` if the attack fails or the field is above [`MAX_FIELD_SIZE`]. Fails for
+/// an exponent below 2 and if `min(e^r, p - 1)` exceeds [`MAX_ATTACK_DEGREE`].
+pub fn interpolation_attack<F: PrimeField>(
+    exponent: usize,
+    round_keys: &[F],
+    oracle: impl Fn(F) -> F,
+) -> Result<Option<F>, MiMCError> {
+    if exponent < 2 {
+        return Err(MiMCError::InvalidExponent(exponent));
+    }
+    let Some(p) = field_size::<F>() else {
+        return Ok(None);
+    };
+    let degree = (exponent as u64)
+        .checked_pow(round_keys.len() as u32)
+        .filter(|d| *d < p)
+        .unwrap_or(p - 1);
+    check_degree(degree)?;
+    let points: Vec<(F, F)> = (0..=degree)
+        .map(|x| (F::from(x), oracle(F::from(x))))
+        .collect();
+    let k = interpolate(&points)[degree as usize - 1] / F::from(degree);
+
+    let candidate = DynMiMC::try_new_unvalidated(
+        PermutationType::NonFeistel,
+        exponent,
+        1,
+        k,
+        round_keys.to_vec(),
+    )?;
+    Ok(points
+        .iter()
+        .all(|(x, y)| encrypt(&candidate, *x) == *y)
+        .then_some(k))
+}
+
+/// Recover the key of [`encrypt`] from two known pairs as the root of
+/// `gcd(E_K(x_1) - y_1, E_K(x_2) - y_2)`, polynomials of degree `e^r` in `K`.
+/// Gives up with `None` once `e^r >= p`, where they are larger than the key space
+/// and trying every key is cheaper, if the gcd has more than one root, or if the
+/// field is above [`MAX_FIELD_SIZE`]. Fails for an exponent below 2 and if `e^r`
+/// exceeds [`MAX_ATTACK_DEGREE`].
+pub fn gcd_attack<F: PrimeField>(
+    exponent: usize,
+    round_keys: &[F],
+    pairs: [(F, F); 2],
+) -> Result<Option<F>, MiMCError> {
+    if exponent < 2 {
+        return Err(MiMCError::InvalidExponent(exponent));
+    }
+    let Some(p) = field_size::<F>() else {
+        return Ok(None);
+    };
+    let Some(degree) = (exponent as u64)
+        .checked_pow(round_keys.len() as u32)
+        .filter(|d| *d < p)
+    else {
+        return Ok(None);
+    };
+    check_degree(degree)?;
+
+    let [f, g] = pairs.map(|(x, y)| {
+        let mut f = key_polynomial(exponent, round_keys, x);
+        f[0] -= y;
+        f
+    });
+    Ok(match poly_gcd(f, g).as_slice() {
+        [c, one] if one.is_one() => Some(-*c),
+        _ => None,
+    })
+}
+
+fn check_degree(degree: u64) -> Result<(), MiMCError> {
+    match degree > MAX_ATTACK_DEGREE {
+        true => Err(MiMCError::AttackDegreeTooHigh {
+            degree,
+            maximum: MAX_ATTACK_DEGREE,
+        }),
+        false => Ok(()),
+    }
+}
+
+/// `p`, if at most [`MAX_FIELD_SIZE`]
+fn field_size<F: PrimeField>() -> Option<u64> {
+    match F::MODULUS.as_ref() {
+        [p, rest @ ..] if rest.iter().all(|l| *l == 0) && *p <= MAX_FIELD_SIZE => Some(*p),
+        _ => None,
+    }
+}
+
+/// `E_K(x)` as a polynomial in the key `K`, following [`non_feistel_rounds`]
+fn key_polynomial<F: Field>(exponent: usize, round_keys: &[F], x: F) -> Vec<F> {
+    let key = vec![F::zero(), F::one()];
+    let mut r = vec![];
+    for (i, round_key) in round_keys.iter().enumerate() {
+        let t = match i == 0 {
+            true => poly_add(&key, &[x]),
+            false => poly_add(&poly_add(&key, &r), &[*round_key]),
+        };
+        r = exp_chain(&t, exponent, |a, b| poly_mul(a, b));
+    }
+    poly_add(&r, &key)
+}
+
+/// Coefficients, constant first, of the polynomial through `points` by Newton's
+/// divided differences
+fn interpolate<F: Field>(points: &[(F, F)]) -> Vec<F> {
+    let n = points.len();
+    let mut differences: Vec<F> = points.iter().map(|(_, y)| *y).collect();
+    for j in 1..n {
+        for i in (j..n).rev() {
+            differences[i] = (differences[i] - differences[i - 1])
+                / (points[i].0 - points[i - j].0);
+        }
+    }
+    let mut coefficients = vec![F::zero(); n];
+    for i in (0..n).rev() {
+        for j in (1..n).rev() {
+            coefficients[j] = coefficients[j - 1] - points[i].0 * coefficients[j];
+        }
+        coefficients[0] = differences[i] - points[i].0 * coefficients[0];
+    }
+    coefficients
+}
+
+fn poly_trim<F: Field>(mut a: Vec<F>) -> Vec<F> {
+    while a.last().is_some_and(|c| c.is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+fn poly_add<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = long.to_vec();
+    for (s, c) in sum.iter_mut().zip(short) {
+        *s += c;
+    }
+    poly_trim(sum)
+}
+
+fn poly_mul<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut product = vec![F::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] += *x * y;
+        }
+    }
+    poly_trim(product)
+}
+
+/// `a mod b`, `b` nonzero
+fn poly_rem<F: Field>(mut a: Vec<F>, b: &[F]) -> Vec<F> {
+    let lead = b.last().expect("nonzero divisor").inverse().expect("trimmed");
+    while a.len() >= b.len() {
+        let factor = *a.last().unwrap() * lead;
+        let shift = a.len() - b.len();
+        for (i, c) in b.iter().enumerate() {
+            a[shift + i] -= factor * c;
+        }
+        a.pop();
+        a = poly_trim(a);
+    }
+    a
+}
+
+/// Monic gcd
+fn poly_gcd<F: Field>(a: Vec<F>, b: Vec<F>) -> Vec<F> {
+    let (mut a, mut b) = (poly_trim(a), poly_trim(b));
+    while !b.is_empty() {
+        (a, b) = (b.clone(), poly_rem(a, &b));
+    }
+    match a.last().and_then(|c| c.inverse()) {
+        Some(lead) => a.iter().map(|c| *c * lead).collect(),
+        None => a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::{PrimeField, Zero};
+    use ark_std::test_rng;
+
+    use super::{
+        encrypt, gcd_attack, interpolate, interpolation_attack, F101, F65537, MAX_ATTACK_DEGREE,
+    };
+    use crate::{validation::min_rounds, DynMiMC, MiMCError, PermutationType};
+
+    fn cipher<F: PrimeField>(rounds: usize) -> DynMiMC<F> {
+        let rng = &mut test_rng();
+        let round_keys = (0..rounds)
+            .map(|i| match i {
+                0 => F::zero(),
+                _ => F::rand(rng),
+            })
+            .collect();
+        DynMiMC::try_new_unvalidated(PermutationType::NonFeistel, 3, 1, F::rand(rng), round_keys)
+            .unwrap()
+    }
+
+    fn attack<F: PrimeField>(rounds: usize) -> (Option<F>, Option<F>, F) {
+        let mimc = cipher::<F>(rounds);
+        let oracle = |x| encrypt(&mimc, x);
+        let pairs = [F::from(1u64), F::from(2u64)].map(|x| (x, oracle(x)));
+        (
+            interpolation_attack(3, mimc.round_keys(), oracle).unwrap(),
+            gcd_attack(3, mimc.round_keys(), pairs).unwrap(),
+            mimc.k(),
+        )
+    }
+
+    #[test]
+    fn encrypt_matches_sponge() {
+        let mimc = cipher::<F65537>(4);
+        let x = F65537::from(7u64);
+        assert_eq!(mimc.permute(vec![x])[0], mimc.k() + x + encrypt(&mimc, x));
+    }
+
+    #[test]
+    fn interpolation() {
+        let points: Vec<_> = [(1u64, 3u64), (2, 7), (5, 31)]
+            .iter()
+            .map(|(x, y)| (F101::from(*x), F101::from(*y)))
+            .collect();
+        // x^2 + x + 1
+        assert_eq!(interpolate(&points), vec![F101::from(1u64); 3]);
+    }
+
+    #[test]
+    fn attacks_below_round_bound() {
+        for rounds in 1..min_rounds::<F101>(3).unwrap() {
+            let (interpolation, gcd, k) = attack::<F101>(rounds);
+            assert_eq!(interpolation, Some(k));
+            assert_eq!(gcd, Some(k));
+        }
+        for rounds in 1..=5 {
+            let (interpolation, gcd, k) = attack::<F65537>(rounds);
+            assert_eq!(interpolation, Some(k));
+            assert_eq!(gcd, Some(k));
+        }
+    }
+
+    #[test]
+    fn attacks_above_round_bound() {
+        let bound = min_rounds::<F101>(3).unwrap();
+        assert_eq!(bound, 5);
+        for rounds in bound..bound + 3 {
+            assert_eq!(attack::<F101>(rounds).0, None);
+            assert_eq!(attack::<F101>(rounds).1, None);
+        }
+
+        let bound = min_rounds::<F65537>(3).unwrap();
+        assert_eq!(bound, 11);
+        for rounds in bound..bound + 3 {
+            let mimc = cipher::<F65537>(rounds);
+            let oracle = |x| encrypt(&mimc, x);
+            let pairs = [F65537::from(1u64), F65537::from(2u64)].map(|x| (x, oracle(x)));
+            assert_eq!(gcd_attack(3, mimc.round_keys(), pairs), Ok(None));
+            // The whole codebook is above MAX_ATTACK_DEGREE
+            assert_eq!(
+                interpolation_attack(3, mimc.round_keys(), oracle),
+                Err(MiMCError::AttackDegreeTooHigh {
+                    degree: 65536,
+                    maximum: MAX_ATTACK_DEGREE
+                })
+            );
+        }
+        assert_eq!(interpolation_attack(3, &[ark_bn254::Fr::zero()], |x| x), Ok(None));
+    }
+
+    #[test]
+    fn attack_errors() {
+        let mimc = cipher::<F65537>(11);
+        let pairs = [(F65537::from(1u64), F65537::from(2u64)); 2];
+        for exponent in [0, 1] {
+            assert_eq!(
+                interpolation_attack(exponent, mimc.round_keys(), |x| x),
+                Err(MiMCError::InvalidExponent(exponent))
+            );
+            assert_eq!(
+                gcd_attack(exponent, mimc.round_keys(), pairs),
+                Err(MiMCError::InvalidExponent(exponent))
+            );
+        }
+        // The whole codebook of F65537, and 3^8 > MAX_ATTACK_DEGREE
+        assert_eq!(
+            interpolation_attack(3, mimc.round_keys(), |x| encrypt(&mimc, x)),
+            Err(MiMCError::AttackDegreeTooHigh {
+                degree: 65536,
+                maximum: MAX_ATTACK_DEGREE
+            })
+        );
+        assert_eq!(
+            gcd_attack(3, &mimc.round_keys()[..8], pairs),
+            Err(MiMCError::AttackDegreeTooHigh {
+                degree: 6561,
+                maximum: MAX_ATTACK_DEGREE
+            })
+        );
+    }
+}
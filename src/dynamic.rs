@@ -0,0 +1,347 @@
+use ark_ff::PrimeField;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    absorb_feistel, absorb_non_feistel, exp_chain, round_constants, sponge_feistel,
+    sponge_non_feistel, validate_parameters, validate_round_keys, MiMC, MiMCError,
+    MiMCParameters, PermutationType,
+};
+
+/// [`MiMC`] with the exponent, round count and permutation type held as values,
+/// for parameters only known at runtime such as ones read from a config file.
+/// Produces the same outputs as `MiMC<F, P>` with the same settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynMiMC<F: PrimeField> {
+    pub permutation_type: PermutationType,
+    pub num_outputs: usize,
+    /// Exponent, at least 2
+    exponent: usize,
+    /// Key, see [`DynMiMC::set_k`]
+    k: F,
+    /// Round keys, one per round, see [`DynMiMC::set_round_keys`]
+    round_keys: Vec<F>,
+    /// `k + round_keys[i]` for feistel rounds, `k` for the first round
+    round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> DynMiMC<F> {
+    /// # Panics
+    ///
+    /// If [`DynMiMC::try_new`] fails
+    pub fn new(
+        permutation_type: PermutationType,
+        exponent: usize,
+        num_outputs: usize,
+        k: F,
+        round_keys: Vec<F>,
+    ) -> Self {
+        Self::try_new(permutation_type, exponent, num_outputs, k, round_keys)
+            .expect("Invalid MiMC parameters")
+    }
+
+    /// Checks [`validate_parameters`] with one round per round key and [`validate_round_keys`]
+    pub fn try_new(
+        permutation_type: PermutationType,
+        exponent: usize,
+        num_outputs: usize,
+        k: F,
+        round_keys: Vec<F>,
+    ) -> Result<Self, MiMCError> {
+        validate_parameters::<F>(exponent, round_keys.len())?;
+        validate_round_keys(&round_keys)?;
+        Self::try_new_unvalidated(permutation_type, exponent, num_outputs, k, round_keys)
+    }
+
+    /// Only checks that the exponent is at least 2 and there is at least one round,
+    /// for reduced-round experiments and tests. The parameters may not be a secure permutation.
+    pub fn try_new_unvalidated(
+        permutation_type: PermutationType,
+        exponent: usize,
+        num_outputs: usize,
+        k: F,
+        round_keys: Vec<F>,
+    ) -> Result<Self, MiMCError> {
+        if exponent < 2 {
+            return Err(MiMCError::InvalidExponent(exponent));
+        }
+        if round_keys.is_empty() {
+            return Err(MiMCError::TooFewRounds {
+                rounds: 0,
+                minimum: 1,
+            });
+        }
+        let round_constants = round_constants(k, &round_keys);
+        Ok(Self {
+            permutation_type,
+            exponent,
+            num_outputs,
+            k,
+            round_keys,
+            round_constants,
+        })
+    }
+
+    /// Typed parameters with the same key and round keys for `permutation_type`
+    pub fn from_typed<P: MiMCParameters>(
+        mimc: &MiMC<F, P>,
+        permutation_type: PermutationType,
+    ) -> Self {
+        Self {
+            permutation_type,
+            exponent: P::EXPONENT,
+            num_outputs: mimc.num_outputs,
+            k: mimc.k(),
+            round_keys: mimc.round_keys().to_vec(),
+            round_constants: mimc.round_constants.clone(),
+        }
+    }
+
+    /// Typed parameters with the same key and round keys, if the exponent and
+    /// round count match `P`. The permutation type is left to the caller.
+    pub fn to_typed<P: MiMCParameters>(&self) -> Result<MiMC<F, P>, MiMCError> {
+        if self.exponent != P::EXPONENT {
+            return Err(MiMCError::ExponentMismatch {
+                expected: P::EXPONENT,
+                actual: self.exponent,
+            });
+        }
+        MiMC::try_new_unvalidated(self.num_outputs, self.k, self.round_keys.clone())
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len()
+    }
+
+    pub fn exponent(&self) -> usize {
+        self.exponent
+    }
+
+    pub fn k(&self) -> F {
+        self.k
+    }
+
+    pub fn round_keys(&self) -> &[F] {
+        &self.round_keys
+    }
+
+    /// Replace the key and rebuild the round constants
+    pub fn set_k(&mut self, k: F) {
+        self.k = k;
+        self.round_constants = round_constants(k, &self.round_keys);
+    }
+
+    /// Replace the round keys, which also sets the round count, and rebuild the
+    /// round constants. Only checks as [`DynMiMC::try_new_unvalidated`].
+    pub fn set_round_keys(&mut self, round_keys: Vec<F>) -> Result<(), MiMCError> {
+        *self = Self::try_new_unvalidated(
+            self.permutation_type,
+            self.exponent,
+            self.num_outputs,
+            self.k,
+            round_keys,
+        )?;
+        Ok(())
+    }
+
+    /// `t^exponent` with the same chain as the R1CS gadget
+    fn pow(&self) -> impl FnMut(F) -> F {
+        let exponent = self.exponent;
+        move |t| exp_chain(&t, exponent, |a, b| *a * b)
+    }
+
+    /// Permute `state` with [`DynMiMC::permutation_type`]
+    pub fn permute(&self, state: Vec<F>) -> Vec<F> {
+        match self.permutation_type {
+            PermutationType::Feistel => {
+                sponge_feistel(&self.round_constants, self.num_outputs, state, &mut self.pow())
+            }
+            PermutationType::NonFeistel => sponge_non_feistel(
+                &self.round_keys,
+                self.k,
+                self.num_outputs,
+                state,
+                &mut self.pow(),
+            ),
+        }
+    }
+
+    /// Two-to-one compression, the first output of permuting `[left, right]`
+    pub fn compress(&self, left: F, right: F) -> F {
+        self.permute(vec![left, right])[0]
+    }
+
+    /// [`DynMiMC::compress`] of `inputs`, which must hold exactly two elements
+    pub fn compress_inputs(&self, inputs: &[F]) -> Result<F, MiMCError> {
+        match inputs {
+            [left, right] => Ok(self.compress(*left, *right)),
+            _ => Err(MiMCError::CompressArity(inputs.len())),
+        }
+    }
+
+    /// [`DynMiMC::compress_inputs`] of every input, in parallel with the `parallel`
+    /// feature. Outputs are in the same order as `inputs`.
+    pub fn compress_many(&self, inputs: &[Vec<F>]) -> Result<Vec<F>, MiMCError> {
+        if let Some(input) = inputs.iter().find(|input| input.len() != 2) {
+            return Err(MiMCError::CompressArity(input.len()));
+        }
+        #[cfg(feature = "parallel")]
+        let iter = inputs.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let iter = inputs.iter();
+        Ok(iter.map(|input| self.compress(input[0], input[1])).collect())
+    }
+
+    /// Permute every input independently, in parallel with the `parallel` feature.
+    /// Outputs are in the same order as `inputs`.
+    pub fn hash_many(&self, inputs: &[Vec<F>]) -> Vec<Vec<F>> {
+        #[cfg(feature = "parallel")]
+        let iter = inputs.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let iter = inputs.iter();
+        iter.map(|input| self.permute(input.clone())).collect()
+    }
+
+    /// Compress adjacent pairs of `nodes` into the next tree level,
+    /// in parallel with the `parallel` feature.
+    pub fn compress_level(&self, nodes: &[F]) -> Result<Vec<F>, MiMCError> {
+        if !nodes.len().is_multiple_of(2) {
+            return Err(MiMCError::OddTreeLevel(nodes.len()));
+        }
+        #[cfg(feature = "parallel")]
+        let iter = nodes.par_chunks(2);
+        #[cfg(not(feature = "parallel"))]
+        let iter = nodes.chunks(2);
+        Ok(iter.map(|pair| self.compress(pair[0], pair[1])).collect())
+    }
+
+    /// Feistel sponge state `(r, c)` after absorbing `state`
+    pub(crate) fn absorb_feistel(&self, state: &[F]) -> (F, F) {
+        absorb_feistel(&self.round_constants, state, &mut self.pow())
+    }
+
+    /// Non-feistel sponge state after absorbing `state`
+    pub(crate) fn absorb_non_feistel(&self, state: &[F]) -> F {
+        absorb_non_feistel(&self.round_keys, self.k, state, &mut self.pow())
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> MiMC<F, P> {
+    /// Runtime parameters with the same key and round keys for `permutation_type`
+    pub fn to_dyn(&self, permutation_type: PermutationType) -> DynMiMC<F> {
+        DynMiMC::from_typed(self, permutation_type)
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> TryFrom<&DynMiMC<F>> for MiMC<F, P> {
+    type Error = MiMCError;
+
+    fn try_from(mimc: &DynMiMC<F>) -> Result<Self, Self::Error> {
+        mimc.to_typed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_377::Fr;
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{MiMC, MiMCError, MiMCParameters, PermutationType};
+
+    use super::DynMiMC;
+
+    macro_rules! mimc_mock {
+        ($name:ident, $exp:literal) => {
+            #[derive(Clone, Default)]
+            struct $name;
+
+            impl MiMCParameters for $name {
+                const ROUNDS: usize = 9;
+                const EXPONENT: usize = $exp;
+            }
+        };
+    }
+
+    mimc_mock!(MiMCMock5, 5);
+    mimc_mock!(MiMCMock11, 11);
+
+    fn check_matches_typed<P: MiMCParameters>() {
+        let rng = &mut test_rng();
+        let mut mimc = MiMC::<Fr, P>::rand_unvalidated(rng);
+        mimc.num_outputs = 3;
+        let state = (0..3).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+        for permutation_type in [PermutationType::Feistel, PermutationType::NonFeistel] {
+            let dyn_mimc = mimc.to_dyn(permutation_type);
+            assert_eq!(dyn_mimc.rounds(), P::ROUNDS);
+            assert_eq!(
+                dyn_mimc.permute(state.clone()),
+                mimc.permute(permutation_type, state.clone())
+            );
+            assert_eq!(
+                dyn_mimc.compress(state[0], state[1]),
+                mimc.compress(permutation_type, state[0], state[1])
+            );
+
+            let built = DynMiMC::try_new_unvalidated(
+                permutation_type,
+                P::EXPONENT,
+                3,
+                mimc.k,
+                mimc.round_keys.clone(),
+            )
+            .unwrap();
+            assert_eq!(built, dyn_mimc);
+            assert_eq!(MiMC::<Fr, P>::try_from(&built), Ok(mimc.clone()));
+
+            let mut updated = dyn_mimc.clone();
+            let mut changed = mimc.clone();
+            updated.set_k(mimc.k + Fr::from(1));
+            changed.set_k(mimc.k + Fr::from(1));
+            assert_eq!(
+                updated.permute(state.clone()),
+                changed.permute(permutation_type, state.clone())
+            );
+            assert!(updated.set_round_keys(vec![]).is_err());
+            updated.set_round_keys(mimc.round_keys.clone()).unwrap();
+            assert_eq!(updated, changed.to_dyn(permutation_type));
+        }
+    }
+
+    #[test]
+    fn matches_typed() {
+        check_matches_typed::<MiMCMock5>();
+        check_matches_typed::<MiMCMock11>();
+    }
+
+    #[test]
+    fn errors() {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock5>::rand_unvalidated(rng).to_dyn(PermutationType::Feistel);
+
+        assert_eq!(mimc.exponent(), 5);
+        assert_eq!(
+            mimc.to_typed::<MiMCMock11>(),
+            Err(MiMCError::ExponentMismatch {
+                expected: 11,
+                actual: 5
+            })
+        );
+        assert_eq!(
+            DynMiMC::try_new(PermutationType::Feistel, 11, 1, mimc.k, mimc.round_keys.clone()),
+            Err(MiMCError::TooFewRounds {
+                rounds: 9,
+                minimum: 73
+            })
+        );
+        assert_eq!(
+            DynMiMC::try_new_unvalidated(PermutationType::Feistel, 1, 1, mimc.k, vec![mimc.k]),
+            Err(MiMCError::InvalidExponent(1))
+        );
+        assert_eq!(mimc.compress_inputs(&[mimc.k]), Err(MiMCError::CompressArity(1)));
+        assert_eq!(
+            mimc.compress_many(&[vec![mimc.k; 2], vec![mimc.k; 3]]),
+            Err(MiMCError::CompressArity(3))
+        );
+    }
+}
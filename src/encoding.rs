@@ -0,0 +1,459 @@
+//! JSON import and export of MiMC parameters and round constants, with the `serde` feature.
+//!
+//! Field elements are written as decimal or `0x` hex strings, as circomlibjs and gnark
+//! tooling exchange them, or as little- or big-endian bytes. Values are checked to be
+//! below the modulus instead of being reduced.
+
+use std::io::{Read, Write};
+
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{merkle::MerkleProof, DynMiMC, MiMC, MiMCError, MiMCParameters, PermutationType};
+
+/// How [`MiMCDocument`] and [`save_circomlib_constants`] write field elements
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberEncoding {
+    #[default]
+    Decimal,
+    /// `0x` prefixed lowercase hex without leading zeros, as circomlibjs prints them
+    Hex,
+    LeBytes,
+    BeBytes,
+}
+
+/// Encoded field element
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Number {
+    /// Decimal or `0x` hex string
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Text(text) => write!(f, "{text}"),
+            Number::Bytes(bytes) => write!(f, "{bytes:?}"),
+        }
+    }
+}
+
+impl NumberEncoding {
+    pub fn encode<F: PrimeField>(self, f: &F) -> Number {
+        let n: BigUint = f.into_bigint().into();
+        match self {
+            NumberEncoding::Decimal => Number::Text(n.to_str_radix(10)),
+            NumberEncoding::Hex => Number::Text(format!("0x{}", n.to_str_radix(16))),
+            NumberEncoding::LeBytes => Number::Bytes(f.into_bigint().to_bytes_le()),
+            NumberEncoding::BeBytes => Number::Bytes(f.into_bigint().to_bytes_be()),
+        }
+    }
+
+    /// Strings are read as hex with a `0x` prefix and as decimal otherwise, whatever the
+    /// encoding. Bytes are read with the encoding's endianness and rejected for
+    /// [`NumberEncoding::Decimal`] and [`NumberEncoding::Hex`]. `None` if the value
+    /// does not parse or is not below the modulus.
+    pub fn decode<F: PrimeField>(self, number: &Number) -> Option<F> {
+        let n = match (number, self) {
+            (Number::Text(text), _) => match text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+                Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16)?,
+                None if text.bytes().all(|b| b.is_ascii_digit()) => {
+                    BigUint::parse_bytes(text.as_bytes(), 10)?
+                }
+                None => return None,
+            },
+            (Number::Bytes(bytes), NumberEncoding::LeBytes) => BigUint::from_bytes_le(bytes),
+            (Number::Bytes(bytes), NumberEncoding::BeBytes) => BigUint::from_bytes_be(bytes),
+            (Number::Bytes(_), _) => return None,
+        };
+        F::from_bigint(F::BigInt::try_from(n).ok()?)
+    }
+}
+
+fn decode_round_keys<F: PrimeField>(
+    encoding: NumberEncoding,
+    round_keys: &[Number],
+) -> Result<Vec<F>, MiMCError> {
+    round_keys
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            encoding.decode(c).ok_or_else(|| MiMCError::InvalidRoundKey {
+                index,
+                value: c.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Serde representation of [`MiMC`] and [`DynMiMC`]. `permutation_type` is only
+/// written for [`DynMiMC`] and `preset_id` only for presets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiMCDocument {
+    pub rounds: usize,
+    pub exponent: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permutation_type: Option<PermutationType>,
+    pub num_outputs: usize,
+    #[serde(default)]
+    pub encoding: NumberEncoding,
+    pub k: Number,
+    pub round_keys: Vec<Number>,
+}
+
+impl MiMCDocument {
+    pub fn from_mimc<F: PrimeField, P: MiMCParameters>(
+        mimc: &MiMC<F, P>,
+        encoding: NumberEncoding,
+    ) -> Self {
+        Self {
+            rounds: P::ROUNDS,
+            exponent: P::EXPONENT,
+            preset_id: P::PRESET_ID.map(str::to_string),
+            permutation_type: None,
+            num_outputs: mimc.num_outputs,
+            encoding,
+            k: encoding.encode(&mimc.k),
+            round_keys: mimc.round_keys.iter().map(|c| encoding.encode(c)).collect(),
+        }
+    }
+
+    pub fn from_dyn<F: PrimeField>(mimc: &DynMiMC<F>, encoding: NumberEncoding) -> Self {
+        Self {
+            rounds: mimc.rounds(),
+            exponent: mimc.exponent(),
+            preset_id: None,
+            permutation_type: Some(mimc.permutation_type),
+            num_outputs: mimc.num_outputs,
+            encoding,
+            k: encoding.encode(&mimc.k()),
+            round_keys: mimc.round_keys().iter().map(|c| encoding.encode(c)).collect(),
+        }
+    }
+
+    /// Fails if the rounds, exponent or a recorded preset differ from `P`.
+    /// Like [`MiMC::try_new_unvalidated`], the parameters themselves are not validated.
+    pub fn to_mimc<F: PrimeField, P: MiMCParameters>(&self) -> Result<MiMC<F, P>, MiMCError> {
+        if self.exponent != P::EXPONENT {
+            return Err(MiMCError::ExponentMismatch {
+                expected: P::EXPONENT,
+                actual: self.exponent,
+            });
+        }
+        if let Some(preset_id) = self.preset_id.as_deref().filter(|id| Some(*id) != P::PRESET_ID) {
+            return Err(MiMCError::PresetMismatch(preset_id.to_string()));
+        }
+        let (k, round_keys) = self.decode::<F>(P::ROUNDS)?;
+        MiMC::try_new_unvalidated(self.num_outputs, k, round_keys)
+    }
+
+    /// Fails if `permutation_type` is missing. Like [`DynMiMC::try_new_unvalidated`],
+    /// the parameters themselves are not validated.
+    pub fn to_dyn<F: PrimeField>(&self) -> Result<DynMiMC<F>, MiMCError> {
+        let permutation_type = self
+            .permutation_type
+            .ok_or_else(|| MiMCError::InvalidJson("missing permutation_type".to_string()))?;
+        let (k, round_keys) = self.decode::<F>(self.rounds)?;
+        DynMiMC::try_new_unvalidated(permutation_type, self.exponent, self.num_outputs, k, round_keys)
+    }
+
+    fn decode<F: PrimeField>(&self, rounds: usize) -> Result<(F, Vec<F>), MiMCError> {
+        for actual in [self.rounds, self.round_keys.len()] {
+            if actual != rounds {
+                return Err(MiMCError::InvalidRoundKeysLength {
+                    expected: rounds,
+                    actual,
+                });
+            }
+        }
+        let k = self
+            .encoding
+            .decode(&self.k)
+            .ok_or_else(|| MiMCError::InvalidNumber(self.k.to_string()))?;
+        Ok((k, decode_round_keys(self.encoding, &self.round_keys)?))
+    }
+}
+
+/// Written as a [`MiMCDocument`] with decimal numbers, use [`MiMCDocument::from_mimc`]
+/// for another encoding
+impl<F: PrimeField, P: MiMCParameters> Serialize for MiMC<F, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MiMCDocument::from_mimc(self, NumberEncoding::Decimal).serialize(serializer)
+    }
+}
+
+impl<'de, F: PrimeField, P: MiMCParameters> Deserialize<'de> for MiMC<F, P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MiMCDocument::deserialize(deserializer)?
+            .to_mimc()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Written as a [`MiMCDocument`] with decimal numbers, use [`MiMCDocument::from_dyn`]
+/// for another encoding
+impl<F: PrimeField> Serialize for DynMiMC<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MiMCDocument::from_dyn(self, NumberEncoding::Decimal).serialize(serializer)
+    }
+}
+
+impl<'de, F: PrimeField> Deserialize<'de> for DynMiMC<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MiMCDocument::deserialize(deserializer)?
+            .to_dyn()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Serde representation of a [`MerkleProof`] and the root it leads to. `preset_id`
+/// and `permutation_type` record the hash the tree was built with, for callers
+/// that verify proofs from several trees.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProofDocument {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permutation_type: Option<PermutationType>,
+    pub index: usize,
+    #[serde(default)]
+    pub encoding: NumberEncoding,
+    pub leaf: Number,
+    pub siblings: Vec<Number>,
+    pub root: Number,
+}
+
+impl MerkleProofDocument {
+    /// `proof` and `root` of a tree hashed with the `permutation_type` of the preset
+    /// `preset_id`, either left out when `None`
+    pub fn from_proof<F: PrimeField>(
+        proof: &MerkleProof<F>,
+        root: &F,
+        preset_id: Option<&str>,
+        permutation_type: Option<PermutationType>,
+        encoding: NumberEncoding,
+    ) -> Self {
+        Self {
+            preset_id: preset_id.map(str::to_string),
+            permutation_type,
+            index: proof.index,
+            encoding,
+            leaf: encoding.encode(&proof.leaf),
+            siblings: proof.siblings.iter().map(|f| encoding.encode(f)).collect(),
+            root: encoding.encode(root),
+        }
+    }
+
+    /// The proof and its root, to check with [`MerkleProof::verify`] or
+    /// [`MerkleProof::verify_dyn`]
+    pub fn to_proof<F: PrimeField>(&self) -> Result<(MerkleProof<F>, F), MiMCError> {
+        let decode = |number: &Number| {
+            self.encoding
+                .decode(number)
+                .ok_or_else(|| MiMCError::InvalidNumber(number.to_string()))
+        };
+        Ok((
+            MerkleProof {
+                index: self.index,
+                leaf: decode(&self.leaf)?,
+                siblings: self.siblings.iter().map(decode).collect::<Result<_, _>>()?,
+            },
+            decode(&self.root)?,
+        ))
+    }
+}
+
+/// Round constants from a circomlibjs-style JSON array as written by
+/// [`save_circomlib_constants`] with `encoding`. Decimal and `0x` hex strings are
+/// read with any encoding, byte arrays only with [`NumberEncoding::LeBytes`] and
+/// [`NumberEncoding::BeBytes`], see [`NumberEncoding::decode`].
+pub fn load_circomlib_constants<F: PrimeField>(
+    reader: impl Read,
+    encoding: NumberEncoding,
+) -> Result<Vec<F>, MiMCError> {
+    let round_keys: Vec<Number> =
+        serde_json::from_reader(reader).map_err(|e| MiMCError::InvalidJson(e.to_string()))?;
+    decode_round_keys(encoding, &round_keys)
+}
+
+/// Round constants as a circomlibjs-style JSON array, strings for [`NumberEncoding::Decimal`]
+/// and [`NumberEncoding::Hex`] and byte arrays otherwise
+pub fn save_circomlib_constants<F: PrimeField>(
+    writer: impl Write,
+    round_keys: &[F],
+    encoding: NumberEncoding,
+) -> Result<(), MiMCError> {
+    let round_keys = round_keys.iter().map(|c| encoding.encode(c)).collect::<Vec<_>>();
+    serde_json::to_writer_pretty(writer, &round_keys).map_err(|e| MiMCError::InvalidJson(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use ark_bls12_377::Fr;
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{merkle::MerkleTree, DynMiMC, MiMC, MiMCError, MiMCParameters, PermutationType};
+
+    use super::{
+        load_circomlib_constants, save_circomlib_constants, MerkleProofDocument, MiMCDocument,
+        Number, NumberEncoding,
+    };
+
+    macro_rules! mimc_mock {
+        ($name:ident, $exp:literal) => {
+            #[derive(Clone, Default)]
+            struct $name;
+
+            impl MiMCParameters for $name {
+                const ROUNDS: usize = 5;
+                const EXPONENT: usize = $exp;
+            }
+        };
+    }
+
+    mimc_mock!(MiMCMock, 5);
+    mimc_mock!(MiMCMock7, 7);
+
+    #[test]
+    fn numbers() {
+        let f = Fr::from(255u64);
+        assert_eq!(NumberEncoding::Decimal.encode(&f), Number::Text("255".to_string()));
+        assert_eq!(NumberEncoding::Hex.encode(&f), Number::Text("0xff".to_string()));
+        assert_eq!(NumberEncoding::LeBytes.encode(&f), Number::Bytes(f.into_bigint().to_bytes_le()));
+
+        for encoding in [NumberEncoding::LeBytes, NumberEncoding::BeBytes] {
+            let f = Fr::rand(&mut test_rng());
+            assert_eq!(encoding.decode::<Fr>(&encoding.encode(&f)), Some(f));
+        }
+        let modulus = Number::Text(Fr::MODULUS.to_string());
+        assert_eq!(NumberEncoding::Decimal.decode::<Fr>(&modulus), None);
+        assert_eq!(NumberEncoding::Decimal.decode::<Fr>(&Number::Text("-1".to_string())), None);
+        assert_eq!(NumberEncoding::Hex.decode::<Fr>(&Number::Bytes(vec![1])), None);
+    }
+
+    #[test]
+    fn mimc_round_trip() -> Result<(), Box<dyn Error>> {
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(&mut test_rng());
+
+        let json = serde_json::to_string(&mimc)?;
+        assert_eq!(serde_json::from_str::<MiMC<Fr, MiMCMock>>(&json)?, mimc);
+        assert!(serde_json::from_str::<MiMC<Fr, MiMCMock7>>(&json).is_err());
+
+        for encoding in [
+            NumberEncoding::Hex,
+            NumberEncoding::LeBytes,
+            NumberEncoding::BeBytes,
+        ] {
+            let json = serde_json::to_string(&MiMCDocument::from_mimc(&mimc, encoding))?;
+            assert_eq!(serde_json::from_str::<MiMC<Fr, MiMCMock>>(&json)?, mimc);
+        }
+
+        let dyn_mimc = mimc.to_dyn(PermutationType::Feistel);
+        let json = serde_json::to_string(&dyn_mimc)?;
+        assert_eq!(serde_json::from_str::<DynMiMC<Fr>>(&json)?, dyn_mimc);
+
+        let document = MiMCDocument::from_mimc(&mimc, NumberEncoding::Decimal);
+        assert_eq!(
+            document.to_dyn::<Fr>(),
+            Err(MiMCError::InvalidJson("missing permutation_type".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merkle_proof_round_trip() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng).to_dyn(PermutationType::Feistel);
+        let leaves = (0..3).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let tree = MerkleTree::new_dyn(&mimc, 2, &leaves)?;
+
+        let document = MerkleProofDocument::from_proof(
+            &tree.proof(1)?,
+            &tree.root(),
+            None,
+            None,
+            NumberEncoding::Hex,
+        );
+        let json = serde_json::to_string(&document)?;
+        assert!(!json.contains("preset_id"));
+        assert!(!json.contains("permutation_type"));
+        let named = MerkleProofDocument::from_proof(
+            &tree.proof(1)?,
+            &tree.root(),
+            Some("mimc-test"),
+            Some(mimc.permutation_type),
+            NumberEncoding::Hex,
+        );
+        assert_eq!(named.preset_id.as_deref(), Some("mimc-test"));
+        assert_eq!(named.permutation_type, Some(PermutationType::Feistel));
+        assert_eq!(named.to_proof::<Fr>()?, document.to_proof::<Fr>()?);
+        let (proof, root) = serde_json::from_str::<MerkleProofDocument>(&json)?.to_proof::<Fr>()?;
+        assert_eq!(proof, tree.proof(1)?);
+        assert!(proof.verify_dyn(&mimc, root));
+
+        let mut document = document;
+        document.root = Number::Text("root".to_string());
+        assert_eq!(
+            document.to_proof::<Fr>(),
+            Err(MiMCError::InvalidNumber("root".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn circomlib_constants() -> Result<(), Box<dyn Error>> {
+        let json = r#"["0", "0xff", "20888961410941983456478427210666206549300505294776164667214940546594746570981"]"#;
+        let round_keys = load_circomlib_constants::<Fr>(json.as_bytes(), NumberEncoding::Decimal);
+        // The last constant is a BN254 constant above the BLS12-377 modulus
+        assert_eq!(
+            round_keys,
+            Err(MiMCError::InvalidRoundKey {
+                index: 2,
+                value: "20888961410941983456478427210666206549300505294776164667214940546594746570981"
+                    .to_string()
+            })
+        );
+
+        let round_keys =
+            load_circomlib_constants::<Fr>(r#"["0", "0xff", "256"]"#.as_bytes(), NumberEncoding::Hex)?;
+        assert_eq!(round_keys, vec![Fr::from(0), Fr::from(255), Fr::from(256)]);
+        Ok(())
+    }
+
+    #[test]
+    fn circomlib_constants_round_trip() -> Result<(), Box<dyn Error>> {
+        let rng = &mut test_rng();
+        let round_keys = (0..4).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        for encoding in [
+            NumberEncoding::Decimal,
+            NumberEncoding::Hex,
+            NumberEncoding::LeBytes,
+            NumberEncoding::BeBytes,
+        ] {
+            let mut saved = vec![];
+            save_circomlib_constants(&mut saved, &round_keys, encoding)?;
+            assert_eq!(load_circomlib_constants::<Fr>(&*saved, encoding)?, round_keys);
+        }
+
+        let mut saved = vec![];
+        save_circomlib_constants(&mut saved, &round_keys, NumberEncoding::LeBytes)?;
+        assert!(matches!(
+            load_circomlib_constants::<Fr>(&*saved, NumberEncoding::Decimal),
+            Err(MiMCError::InvalidRoundKey { index: 0, .. })
+        ));
+        assert!(matches!(
+            load_circomlib_constants::<Fr>("{}".as_bytes(), NumberEncoding::Decimal),
+            Err(MiMCError::InvalidJson(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use ark_relations::r1cs::SynthesisError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MiMCError {
+    /// Number of round keys differs from `MiMCParameters::ROUNDS`
+    InvalidRoundKeysLength { expected: usize, actual: usize },
+    /// Two-to-one inputs of different lengths
+    InputLengthMismatch { left: usize, right: usize },
+    /// Round key constant that does not parse as a field element
+    InvalidRoundKey { index: usize, value: String },
+    /// Exponent below 2, which has no finite round count
+    InvalidExponent(usize),
+    /// Exponent differing from `MiMCParameters::EXPONENT`
+    ExponentMismatch { expected: usize, actual: usize },
+    /// Exponent sharing a factor with `p - 1`, so `x^e` is not a permutation
+    ExponentNotPermutation(usize),
+    /// Fewer rounds than `ceil(log_e p)`
+    TooFewRounds { rounds: usize, minimum: usize },
+    /// Security level above the bit size of the field
+    SecurityLevelTooHigh { bits: usize, maximum: usize },
+    /// First round key is not zero
+    NonZeroFirstRoundKey,
+    /// Nonzero round key repeated at two indices
+    DuplicateRoundKey { first: usize, second: usize },
+    /// No enabled preset with this name
+    UnknownPreset(String),
+    /// Preset requested over a field other than its own
+    PresetFieldMismatch(String),
+    /// Preset recorded in serialized parameters differs from `MiMCParameters::PRESET_ID`
+    PresetMismatch(String),
+    /// Bundled preset round key differing from the one regenerated by `paramgen`
+    RoundKeyMismatch { preset: String, index: usize },
+    /// Field element that does not parse or is not below the modulus
+    InvalidNumber(String),
+    /// Malformed JSON parameters or constants
+    InvalidJson(String),
+    /// More leaves than a tree of the given depth holds
+    TooManyLeaves { depth: usize, leaves: usize },
+    /// Tree level with an odd number of nodes
+    OddTreeLevel(usize),
+    /// Leaf index outside a tree of the given number of leaves
+    LeafIndexOutOfRange { index: usize, leaves: usize },
+    /// Cryptanalysis polynomial above `cryptanalysis::MAX_ATTACK_DEGREE`
+    AttackDegreeTooHigh { degree: u64, maximum: u64 },
+    /// Compression of a number of inputs other than two
+    CompressArity(usize),
+    /// Tree depth above `merkle::MAX_DEPTH`
+    TreeTooDeep { depth: usize, maximum: usize },
+    /// Bundled preset whose exponent differs from the one in its name
+    PresetExponentMismatch { preset: String, exponent: usize },
+}
+
+impl std::fmt::Display for MiMCError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MiMCError::InvalidRoundKeysLength { expected, actual } => {
+                write!(f, "invalid round keys length {actual}, expected {expected}")
+            }
+            MiMCError::InputLengthMismatch { left, right } => {
+                write!(f, "left input length {left} differs from right input length {right}")
+            }
+            MiMCError::InvalidRoundKey { index, value } => {
+                write!(f, "invalid round key {value:?} at index {index}")
+            }
+            MiMCError::InvalidExponent(exponent) => write!(f, "invalid exponent {exponent}"),
+            MiMCError::ExponentMismatch { expected, actual } => {
+                write!(f, "exponent {actual} differs from the expected {expected}")
+            }
+            MiMCError::ExponentNotPermutation(exponent) => {
+                write!(f, "x^{exponent} is not a permutation, {exponent} shares a factor with p - 1")
+            }
+            MiMCError::TooFewRounds { rounds, minimum } => {
+                write!(f, "{rounds} rounds is below the minimum of {minimum}")
+            }
+            MiMCError::SecurityLevelTooHigh { bits, maximum } => {
+                write!(f, "{bits}-bit security exceeds the {maximum}-bit field")
+            }
+            MiMCError::NonZeroFirstRoundKey => write!(f, "first round key is not zero"),
+            MiMCError::DuplicateRoundKey { first, second } => {
+                write!(f, "round keys at index {first} and {second} are equal")
+            }
+            MiMCError::UnknownPreset(name) => write!(f, "unknown or disabled preset {name:?}"),
+            MiMCError::PresetFieldMismatch(name) => {
+                write!(f, "preset {name:?} is defined over a different field")
+            }
+            MiMCError::PresetMismatch(preset) => {
+                write!(f, "parameters of preset {preset:?} do not belong to this preset")
+            }
+            MiMCError::RoundKeyMismatch { preset, index } => {
+                write!(f, "round key {index} of {preset} differs from paramgen")
+            }
+            MiMCError::InvalidNumber(value) => write!(f, "invalid field element {value:?}"),
+            MiMCError::InvalidJson(message) => write!(f, "invalid JSON: {message}"),
+            MiMCError::TooManyLeaves { depth, leaves } => {
+                write!(f, "{leaves} leaves do not fit in a tree of depth {depth}")
+            }
+            MiMCError::OddTreeLevel(len) => write!(f, "tree level has odd length {len}"),
+            MiMCError::LeafIndexOutOfRange { index, leaves } => {
+                write!(f, "leaf index {index} is outside a tree of {leaves} leaves")
+            }
+            MiMCError::AttackDegreeTooHigh { degree, maximum } => {
+                write!(f, "attack polynomial of degree {degree} exceeds the maximum of {maximum}")
+            }
+            MiMCError::CompressArity(len) => {
+                write!(f, "compression takes 2 inputs, got {len}")
+            }
+            MiMCError::TreeTooDeep { depth, maximum } => {
+                write!(f, "tree depth {depth} exceeds the maximum of {maximum}")
+            }
+            MiMCError::PresetExponentMismatch { preset, exponent } => {
+                write!(f, "preset {preset} uses x^{exponent}, not the exponent in its name")
+            }
+        }
+    }
+}
+
+impl ark_std::error::Error for MiMCError {}
+
+/// `SynthesisError` carries no details, every parameter error is reported as
+/// [`SynthesisError::Unsatisfiable`]
+impl From<MiMCError> for SynthesisError {
+    fn from(_: MiMCError) -> Self {
+        SynthesisError::Unsatisfiable
+    }
+}
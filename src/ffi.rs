@@ -0,0 +1,303 @@
+//! C ABI over [`DynMiMC`] for the bundled presets, with the `ffi` feature. The
+//! header is `include/arkworks_mimc.h`, generated by cbindgen from this module.
+//!
+//! Field elements are [`MIMC_FIELD_BYTES`] little-endian bytes below the modulus,
+//! as [`crate::encoding::NumberEncoding::LeBytes`] writes them. Every function
+//! returns a [`MiMCStatus`] and writes its results through pointers; panics are
+//! caught and reported as [`MiMCStatus::Panic`].
+
+use std::{
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use ark_ff::{BigInteger, PrimeField};
+
+use crate::{params, DynMiMC, MiMCError};
+
+/// Bytes of one field element in every bundled field
+pub const MIMC_FIELD_BYTES: usize = 32;
+
+/// Largest `num_outputs` of [`mimc_hash`]
+pub const MIMC_MAX_OUTPUTS: usize = 1 << 16;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiMCStatus {
+    Ok = 0,
+    /// A required pointer is null
+    NullPointer = 1,
+    /// The preset name is not UTF-8 or not a bundled preset
+    UnknownPreset = 2,
+    /// Bytes that are not a field element below the modulus
+    InvalidFieldElement = 3,
+    /// No inputs, no or more than `MIMC_MAX_OUTPUTS` outputs, or more input bytes
+    /// than a buffer can hold
+    InvalidLength = 4,
+    /// Internal error, the outputs are unspecified
+    Panic = 5,
+}
+
+/// Opaque hash of one preset, from [`mimc_new`] and released with [`mimc_free`]
+pub struct MiMCHandle(Hasher);
+
+enum Hasher {
+    Bn254(DynMiMC<ark_bn254::Fr>),
+    Bls12_381(DynMiMC<ark_bls12_381::Fr>),
+    Bls12_377(DynMiMC<ark_bls12_377::Fr>),
+}
+
+/// Create a handle for the preset named `preset`, such as `"mimc-7-91-bn254"`,
+/// with key zero, and store it in `*out`.
+///
+/// # Safety
+///
+/// `preset` is a NUL-terminated string and `out` is valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mimc_new(preset: *const c_char, out: *mut *mut MiMCHandle) -> MiMCStatus {
+    guard(|| {
+        if preset.is_null() || out.is_null() {
+            return Err(MiMCStatus::NullPointer);
+        }
+        let name = CStr::from_ptr(preset)
+            .to_str()
+            .map_err(|_| MiMCStatus::UnknownPreset)?;
+        let hasher = if name.ends_with("-bn254") {
+            params::by_name(name).map(Hasher::Bn254)
+        } else if name.ends_with("-bls12-381") {
+            params::by_name(name).map(Hasher::Bls12_381)
+        } else if name.ends_with("-bls12-377") {
+            params::by_name(name).map(Hasher::Bls12_377)
+        } else {
+            Err(MiMCError::UnknownPreset(name.to_string()))
+        };
+        let hasher = hasher.map_err(|_| MiMCStatus::UnknownPreset)?;
+        *out = Box::into_raw(Box::new(MiMCHandle(hasher)));
+        Ok(())
+    })
+}
+
+/// Release a handle from [`mimc_new`], null is ignored.
+///
+/// # Safety
+///
+/// `handle` is null or from [`mimc_new`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn mimc_free(handle: *mut MiMCHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Permute `num_inputs` field elements with the preset's permutation type and
+/// write the first `num_outputs` elements of the state to `outputs`, at most
+/// [`MIMC_MAX_OUTPUTS`].
+///
+/// # Safety
+///
+/// `handle` is from [`mimc_new`], `inputs` holds `num_inputs * MIMC_FIELD_BYTES`
+/// bytes and `outputs` has room for `num_outputs * MIMC_FIELD_BYTES`.
+#[no_mangle]
+pub unsafe extern "C" fn mimc_hash(
+    handle: *const MiMCHandle,
+    inputs: *const u8,
+    num_inputs: usize,
+    outputs: *mut u8,
+    num_outputs: usize,
+) -> MiMCStatus {
+    guard(|| {
+        if handle.is_null() || inputs.is_null() || outputs.is_null() {
+            return Err(MiMCStatus::NullPointer);
+        }
+        if num_inputs == 0 || num_outputs == 0 || num_outputs > MIMC_MAX_OUTPUTS {
+            return Err(MiMCStatus::InvalidLength);
+        }
+        // `slice::from_raw_parts` requires at most `isize::MAX` bytes
+        let input_bytes = num_inputs
+            .checked_mul(MIMC_FIELD_BYTES)
+            .filter(|bytes| *bytes <= isize::MAX as usize)
+            .ok_or(MiMCStatus::InvalidLength)?;
+        let inputs = slice::from_raw_parts(inputs, input_bytes);
+        let outputs = slice::from_raw_parts_mut(outputs, num_outputs * MIMC_FIELD_BYTES);
+        match &(*handle).0 {
+            Hasher::Bn254(mimc) => hash(mimc, inputs, outputs, num_outputs),
+            Hasher::Bls12_381(mimc) => hash(mimc, inputs, outputs, num_outputs),
+            Hasher::Bls12_377(mimc) => hash(mimc, inputs, outputs, num_outputs),
+        }
+    })
+}
+
+/// Two-to-one compression of `left` and `right` into `out`, as
+/// [`DynMiMC::compress`].
+///
+/// # Safety
+///
+/// `handle` is from [`mimc_new`] and the other pointers hold
+/// `MIMC_FIELD_BYTES` bytes each.
+#[no_mangle]
+pub unsafe extern "C" fn mimc_compress(
+    handle: *const MiMCHandle,
+    left: *const u8,
+    right: *const u8,
+    out: *mut u8,
+) -> MiMCStatus {
+    guard(|| {
+        if handle.is_null() || left.is_null() || right.is_null() || out.is_null() {
+            return Err(MiMCStatus::NullPointer);
+        }
+        let mut inputs = [0; 2 * MIMC_FIELD_BYTES];
+        ptr::copy_nonoverlapping(left, inputs.as_mut_ptr(), MIMC_FIELD_BYTES);
+        ptr::copy_nonoverlapping(
+            right,
+            inputs[MIMC_FIELD_BYTES..].as_mut_ptr(),
+            MIMC_FIELD_BYTES,
+        );
+        let out = slice::from_raw_parts_mut(out, MIMC_FIELD_BYTES);
+        match &(*handle).0 {
+            Hasher::Bn254(mimc) => hash(mimc, &inputs, out, 1),
+            Hasher::Bls12_381(mimc) => hash(mimc, &inputs, out, 1),
+            Hasher::Bls12_377(mimc) => hash(mimc, &inputs, out, 1),
+        }
+    })
+}
+
+fn guard(f: impl FnOnce() -> Result<(), MiMCStatus>) -> MiMCStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => MiMCStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => MiMCStatus::Panic,
+    }
+}
+
+fn hash<F: PrimeField>(
+    mimc: &DynMiMC<F>,
+    inputs: &[u8],
+    outputs: &mut [u8],
+    num_outputs: usize,
+) -> Result<(), MiMCStatus> {
+    let state = inputs
+        .chunks(MIMC_FIELD_BYTES)
+        .map(|bytes| {
+            let mut bigint = F::BigInt::default();
+            bigint
+                .as_mut()
+                .iter_mut()
+                .zip(bytes.chunks(8))
+                .for_each(|(limb, bytes)| {
+                    *limb = u64::from_le_bytes(bytes.try_into().expect("8 byte chunks"))
+                });
+            F::from_bigint(bigint).ok_or(MiMCStatus::InvalidFieldElement)
+        })
+        .collect::<Result<Vec<F>, _>>()?;
+    let mut mimc = mimc.clone();
+    mimc.num_outputs = num_outputs;
+    for (output, f) in outputs
+        .chunks_mut(MIMC_FIELD_BYTES)
+        .zip(mimc.permute(state))
+    {
+        output.copy_from_slice(&f.into_bigint().to_bytes_le());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use ark_bn254::Fr;
+    use ark_ff::{BigInteger, PrimeField};
+
+    use super::{
+        mimc_compress, mimc_free, mimc_hash, mimc_new, MiMCStatus, MIMC_FIELD_BYTES,
+        MIMC_MAX_OUTPUTS,
+    };
+
+    fn bytes(x: u64) -> Vec<u8> {
+        Fr::from(x).into_bigint().to_bytes_le()
+    }
+
+    #[test]
+    fn compress_and_hash() {
+        unsafe {
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                mimc_new(c"mimc-5-220-bn254".as_ptr(), &mut handle),
+                MiMCStatus::Ok
+            );
+
+            let mut out = [0; MIMC_FIELD_BYTES];
+            let status = mimc_compress(
+                handle,
+                bytes(1).as_ptr(),
+                bytes(2).as_ptr(),
+                out.as_mut_ptr(),
+            );
+            assert_eq!(status, MiMCStatus::Ok);
+            let expected: Fr =
+                "19814528709687996974327303300007262407299502847885145507292406548098437687919"
+                    .parse()
+                    .unwrap();
+            assert_eq!(out.to_vec(), expected.into_bigint().to_bytes_le());
+
+            let inputs = [bytes(1), bytes(2)].concat();
+            let mut outputs = [0; 2 * MIMC_FIELD_BYTES];
+            assert_eq!(
+                mimc_hash(handle, inputs.as_ptr(), 2, outputs.as_mut_ptr(), 2),
+                MiMCStatus::Ok
+            );
+            assert_eq!(outputs[..MIMC_FIELD_BYTES], out);
+
+            let modulus = Fr::MODULUS.to_bytes_le();
+            assert_eq!(
+                mimc_compress(
+                    handle,
+                    modulus.as_ptr(),
+                    bytes(2).as_ptr(),
+                    out.as_mut_ptr()
+                ),
+                MiMCStatus::InvalidFieldElement
+            );
+            for (num_inputs, num_outputs) in [
+                (0, 1),
+                (2, MIMC_MAX_OUTPUTS + 1),
+                (usize::MAX, 1),
+                (usize::MAX / MIMC_FIELD_BYTES, 1),
+            ] {
+                assert_eq!(
+                    mimc_hash(
+                        handle,
+                        inputs.as_ptr(),
+                        num_inputs,
+                        outputs.as_mut_ptr(),
+                        num_outputs
+                    ),
+                    MiMCStatus::InvalidLength
+                );
+            }
+            assert_eq!(
+                mimc_hash(handle, ptr::null(), 1, outputs.as_mut_ptr(), 1),
+                MiMCStatus::NullPointer
+            );
+            mimc_free(handle);
+        }
+    }
+
+    #[test]
+    fn unknown_presets() {
+        let mut handle = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                mimc_new(c"mimc-5-220-bn256".as_ptr(), &mut handle),
+                MiMCStatus::UnknownPreset
+            );
+            assert_eq!(
+                mimc_new(c"mimc-5-219-bn254".as_ptr(), &mut handle),
+                MiMCStatus::UnknownPreset
+            );
+            assert_eq!(mimc_new(ptr::null(), &mut handle), MiMCStatus::NullPointer);
+            mimc_free(ptr::null_mut());
+        }
+        assert!(handle.is_null());
+    }
+}
@@ -1,75 +1,463 @@
 use std::marker::PhantomData;
 
 use ark_ff::PrimeField;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 // #[cfg(feature = "r1cs")]     // TODO: Changed to expose for debugging
+pub mod codegen;
 pub mod constraints;
+pub mod cryptanalysis;
+pub mod dynamic;
+#[cfg(feature = "serde")]
+pub mod encoding;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod merkle;
 pub mod params;
+pub mod security;
+mod serialize;
 pub mod utils;
+pub mod validation;
 
-// #[cfg(feature = "paramgen")]
+#[cfg(feature = "paramgen")]
 pub mod paramgen;
 
+pub use error::MiMCError;
+pub use dynamic::DynMiMC;
+pub use validation::{validate, validate_parameters, validate_round_keys};
 pub use traits::*;
 mod traits;
 
-pub trait MiMCParameters: Clone + Default {
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PermutationType {
+    Feistel,
+    #[default]
+    NonFeistel,
+}
+
+/// Square/multiply chain computing `t^exponent` through `mul`, shared by the native and
+/// R1CS round functions so both perform the same multiplications in the same order
+pub(crate) fn exp_chain<T: Clone>(t: &T, exponent: usize, mut mul: impl FnMut(&T, &T) -> T) -> T {
+    match exponent {
+        3 => {
+            let t2 = mul(t, t);
+            mul(&t2, t)
+        }
+        5 => {
+            let t2 = mul(t, t);
+            let t4 = mul(&t2, &t2);
+            mul(&t4, t)
+        }
+        7 => {
+            let t2 = mul(t, t);
+            let t4 = mul(&t2, &t2);
+            let t6 = mul(&t4, &t2);
+            mul(&t6, t)
+        }
+        e => {
+            let mut tn = t.clone();
+            for bit in (0..usize::BITS - 1 - e.leading_zeros()).rev() {
+                tn = mul(&tn, &tn);
+                if (e >> bit) & 1 == 1 {
+                    tn = mul(&tn, t);
+                }
+            }
+            tn
+        }
+    }
+}
+
+pub trait MiMCParameters: Clone + Default + Send + Sync {
     const ROUNDS: usize;
     const EXPONENT: usize;
+    /// Name of the preset these parameters belong to, recorded in the serialization header
+    const PRESET_ID: Option<&'static str> = None;
 }
 
-#[derive(Default, Clone)]        // TODO: Commented out for visibility in src/traits.rs
-// #[derive(Clone)]
+/// No `Default`: there is no meaningful default key schedule, construct with
+/// [`MiMC::try_new`] or a preset
+#[derive(Clone)]
 pub struct MiMC<F: PrimeField, P: MiMCParameters> {
     pub num_outputs: usize,
-    pub k: F,
-    pub round_keys: Vec<F>,
+    /// Key, see [`MiMC::set_k`]
+    k: F,
+    /// Round keys, one per round, see [`MiMC::set_round_keys`]
+    round_keys: Vec<F>,
+    /// `k + round_keys[i]` for feistel rounds, `k` for the first round
+    round_constants: Vec<F>,
     params: PhantomData<P>,
 }
 
 impl<F: PrimeField, P: MiMCParameters> MiMC<F, P> {
+    /// # Panics
+    ///
+    /// If [`MiMC::try_new`] fails
     pub fn new(num_outputs: usize, k: F, round_keys: Vec<F>) -> Self {
-        assert_eq!(round_keys.len(), P::ROUNDS, "Invalid round keys length");
-        Self {
+        Self::try_new(num_outputs, k, round_keys).expect("Invalid MiMC parameters")
+    }
+
+    /// Checks the round keys length, [`validate`] and [`validate_round_keys`]
+    pub fn try_new(num_outputs: usize, k: F, round_keys: Vec<F>) -> Result<Self, MiMCError> {
+        validate::<F, P>()?;
+        validate_round_keys(&round_keys)?;
+        Self::try_new_unvalidated(num_outputs, k, round_keys)
+    }
+
+    /// Only checks the round keys length, for reduced-round experiments and tests.
+    /// The parameters may not be a secure permutation.
+    pub fn try_new_unvalidated(
+        num_outputs: usize,
+        k: F,
+        round_keys: Vec<F>,
+    ) -> Result<Self, MiMCError> {
+        if round_keys.len() != P::ROUNDS {
+            return Err(MiMCError::InvalidRoundKeysLength {
+                expected: P::ROUNDS,
+                actual: round_keys.len(),
+            });
+        }
+        let round_constants = round_constants(k, &round_keys);
+        Ok(Self {
             num_outputs,
             params: PhantomData,
             k,
             round_keys,
+            round_constants,
+        })
+    }
+
+    /// Key zero and one output, for the bundled presets whose round key count
+    /// is fixed by their array type
+    pub(crate) fn from_preset(round_keys: &[F]) -> Self {
+        debug_assert_eq!(round_keys.len(), P::ROUNDS);
+        Self {
+            num_outputs: 1,
+            k: F::zero(),
+            round_keys: round_keys.to_vec(),
+            round_constants: round_constants(F::zero(), round_keys),
+            params: PhantomData,
+        }
+    }
+
+    pub fn k(&self) -> F {
+        self.k
+    }
+
+    pub fn round_keys(&self) -> &[F] {
+        &self.round_keys
+    }
+
+    /// Replace the key and rebuild the round constants
+    pub fn set_k(&mut self, k: F) {
+        self.k = k;
+        self.round_constants = round_constants(k, &self.round_keys);
+    }
+
+    /// Replace the round keys and rebuild the round constants.
+    /// Only checks the length, as [`MiMC::try_new_unvalidated`].
+    pub fn set_round_keys(&mut self, round_keys: Vec<F>) -> Result<(), MiMCError> {
+        *self = Self::try_new_unvalidated(self.num_outputs, self.k, round_keys)?;
+        Ok(())
+    }
+
+    /// `t^EXPONENT` with the shortest square/multiply chain for the common exponents,
+    /// resolved at compile time since `P::EXPONENT` is a constant
+    #[inline(always)]
+    fn pow(t: F) -> F {
+        match P::EXPONENT {
+            3 => t.square() * t,
+            5 => t.square().square() * t,
+            7 => {
+                let t2 = t.square();
+                t2.square() * t2 * t
+            }
+            e => t.pow([e as u64]),
         }
     }
 }
 
 impl<F: PrimeField, P: MiMCParameters> MiMC<F, P> {
+    /// Permute `state` with the given permutation type
+    pub fn permute(&self, permutation_type: PermutationType, state: Vec<F>) -> Vec<F> {
+        match permutation_type {
+            PermutationType::Feistel => self.permute_feistel(state),
+            PermutationType::NonFeistel => self.permute_non_feistel(state),
+        }
+    }
+
+    /// Two-to-one compression, the first output of permuting `[left, right]`
+    pub fn compress(&self, permutation_type: PermutationType, left: F, right: F) -> F {
+        self.permute(permutation_type, vec![left, right])[0]
+    }
+
+    /// Permute every input independently, in parallel with the `parallel` feature.
+    /// Outputs are in the same order as `inputs`.
+    pub fn hash_many(&self, permutation_type: PermutationType, inputs: &[Vec<F>]) -> Vec<Vec<F>> {
+        #[cfg(feature = "parallel")]
+        let iter = inputs.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let iter = inputs.iter();
+        iter.map(|input| self.permute(permutation_type, input.clone()))
+            .collect()
+    }
+
+    /// Compress adjacent pairs of `nodes` into the next tree level,
+    /// in parallel with the `parallel` feature.
+    pub fn compress_level(
+        &self,
+        permutation_type: PermutationType,
+        nodes: &[F],
+    ) -> Result<Vec<F>, MiMCError> {
+        if !nodes.len().is_multiple_of(2) {
+            return Err(MiMCError::OddTreeLevel(nodes.len()));
+        }
+        #[cfg(feature = "parallel")]
+        let iter = nodes.par_chunks(2);
+        #[cfg(not(feature = "parallel"))]
+        let iter = nodes.chunks(2);
+        Ok(iter
+            .map(|pair| self.compress(permutation_type, pair[0], pair[1]))
+            .collect())
+    }
+
+    /// Permute `state`, also returning every product of every round's exponentiation
+    /// in the order of [`exp_chain`], used as witnesses by the R1CS gadget
+    pub(crate) fn permute_traced(
+        &self,
+        permutation_type: PermutationType,
+        state: Vec<F>,
+    ) -> (Vec<F>, Vec<F>) {
+        let mut trace = vec![];
+        let pow = &mut |t: F| {
+            exp_chain(&t, P::EXPONENT, |a, b| {
+                trace.push(*a * b);
+                *a * b
+            })
+        };
+        let outputs = match permutation_type {
+            PermutationType::Feistel => self.permute_feistel_with(state, pow),
+            PermutationType::NonFeistel => self.permute_non_feistel_with(state, pow),
+        };
+        (outputs, trace)
+    }
+
+    /// Feistel sponge state `(r, c)` after absorbing `state`
+    pub(crate) fn absorb_feistel(&self, state: &[F]) -> (F, F) {
+        absorb_feistel(&self.round_constants[..P::ROUNDS], state, &mut Self::pow)
+    }
+
+    /// Non-feistel sponge state after absorbing `state`
+    pub(crate) fn absorb_non_feistel(&self, state: &[F]) -> F {
+        absorb_non_feistel(&self.round_keys[..P::ROUNDS], self.k, state, &mut Self::pow)
+    }
+
     /// MiMC 2n/n x^exp permute
     pub fn permute_feistel(&self, state: Vec<F>) -> Vec<F> {
-        let mut r = F::zero();
-        let mut c = F::zero();
-        for s in state.into_iter() {
-            r += s;
-            (r, c) = self.feistel(r, c);
+        self.permute_feistel_with(state, &mut Self::pow)
+    }
+
+    #[inline(always)]
+    fn permute_feistel_with(&self, state: Vec<F>, pow: &mut impl FnMut(F) -> F) -> Vec<F> {
+        sponge_feistel(&self.round_constants[..P::ROUNDS], self.num_outputs, state, pow)
+    }
+
+    #[cfg(test)]
+    fn feistel(&self, left: F, right: F) -> (F, F) {
+        feistel_rounds(&self.round_constants[..P::ROUNDS], left, right, &mut Self::pow)
+    }
+
+    /// MiMC n/n x^exp permute
+    pub fn permute_non_feistel(&self, state: Vec<F>) -> Vec<F> {
+        self.permute_non_feistel_with(state, &mut Self::pow)
+    }
+
+    #[inline(always)]
+    fn permute_non_feistel_with(&self, state: Vec<F>, pow: &mut impl FnMut(F) -> F) -> Vec<F> {
+        sponge_non_feistel(&self.round_keys[..P::ROUNDS], self.k, self.num_outputs, state, pow)
+    }
+}
+
+/// `k + round_keys[i]` for feistel rounds, `k` for the first round
+pub(crate) fn round_constants<F: PrimeField>(k: F, round_keys: &[F]) -> Vec<F> {
+    round_keys
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match i == 0 {
+            true => k,
+            false => k + c,
+        })
+        .collect()
+}
+
+/// Feistel sponge with one round per round constant, shared by [`MiMC`] and [`DynMiMC`]
+#[inline(always)]
+pub(crate) fn sponge_feistel<F: PrimeField>(
+    round_constants: &[F],
+    num_outputs: usize,
+    state: Vec<F>,
+    pow: &mut impl FnMut(F) -> F,
+) -> Vec<F> {
+    let (mut r, mut c) = absorb_feistel(round_constants, &state, pow);
+    let mut outputs = vec![r];
+    match num_outputs {
+        0 | 1 => outputs,
+        _ => {
+            for _ in 1..num_outputs {
+                (r, c) = feistel_rounds(round_constants, r, c, pow);
+                outputs.push(r);
+            }
+            outputs
         }
-        let mut outputs = vec![r];
-        match self.num_outputs {
-            0 | 1 => outputs,
-            _ => {
-                for _ in 1..self.num_outputs {
-                    (r, c) = self.feistel(r, c);
-                    outputs.push(r);
-                }
-                outputs
+    }
+}
+
+#[inline(always)]
+pub(crate) fn absorb_feistel<F: PrimeField>(
+    round_constants: &[F],
+    state: &[F],
+    pow: &mut impl FnMut(F) -> F,
+) -> (F, F) {
+    let mut r = F::zero();
+    let mut c = F::zero();
+    for s in state {
+        r += s;
+        (r, c) = feistel_rounds(round_constants, r, c, pow);
+    }
+    (r, c)
+}
+
+#[inline(always)]
+pub(crate) fn feistel_rounds<F: PrimeField>(
+    round_constants: &[F],
+    left: F,
+    right: F,
+    pow: &mut impl FnMut(F) -> F,
+) -> (F, F) {
+    let rounds = round_constants.len();
+    let mut x_l = left;
+    let mut x_r = right;
+    for (i, round_constant) in round_constants.iter().enumerate() {
+        let tn = pow(x_l + round_constant);
+        (x_l, x_r) = match i < rounds - 1 {
+            true => (x_r + tn, x_l),
+            false => (x_l, x_r + tn),
+        };
+    }
+    (x_l, x_r)
+}
+
+/// Non-feistel sponge with one round per round key, shared by [`MiMC`] and [`DynMiMC`]
+#[inline(always)]
+pub(crate) fn sponge_non_feistel<F: PrimeField>(
+    round_keys: &[F],
+    k: F,
+    num_outputs: usize,
+    state: Vec<F>,
+    pow: &mut impl FnMut(F) -> F,
+) -> Vec<F> {
+    let mut r = absorb_non_feistel(round_keys, k, &state, pow);
+    let mut outputs = vec![r];
+    match num_outputs {
+        0 | 1 => outputs,
+        _ => {
+            for _ in 1..num_outputs {
+                r += non_feistel_rounds(round_keys, r, r, pow);
+                outputs.push(r);
             }
+            outputs
         }
     }
+}
 
-    fn feistel(&self, left: F, right: F) -> (F, F) {
-        let mut x_l = left;
-        let mut x_r = right;
+#[inline(always)]
+pub(crate) fn absorb_non_feistel<F: PrimeField>(
+    round_keys: &[F],
+    k: F,
+    state: &[F],
+    pow: &mut impl FnMut(F) -> F,
+) -> F {
+    let mut r = k;
+    for s in state {
+        r += *s + non_feistel_rounds(round_keys, *s, r, pow);
+    }
+    r
+}
+
+#[inline(always)]
+pub(crate) fn non_feistel_rounds<F: PrimeField>(
+    round_keys: &[F],
+    x: F,
+    k: F,
+    pow: &mut impl FnMut(F) -> F,
+) -> F {
+    let mut r = F::zero();
+    for (i, round_key) in round_keys.iter().enumerate() {
+        let t = match i == 0 {
+            true => k + x,
+            false => k + r + round_key,
+        };
+        r = pow(t);
+    }
+    r + k
+}
+
+#[cfg(test)]
+impl<F: PrimeField, P: MiMCParameters> MiMC<F, P> {
+    /// Random key and round keys, skipping validation for the reduced-round mocks of tests
+    pub(crate) fn rand_unvalidated<R: ark_std::rand::Rng>(rng: &mut R) -> Self {
+        let round_keys = (0..P::ROUNDS).map(|_| F::rand(rng)).collect();
+        Self::try_new_unvalidated(1, F::rand(rng), round_keys).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_377::Fr;
+    use ark_crypto_primitives::crh::TwoToOneCRHScheme;
+    use ark_ff::{Field, One, Zero};
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{
+        params::round_keys_contants_to_vec, MiMC, MiMCError, MiMCNonFeistelCRH, MiMCParameters,
+        PermutationType,
+    };
+
+    #[derive(Clone, Default)]
+    struct MiMCMock;
+
+    impl MiMCParameters for MiMCMock {
+        const ROUNDS: usize = 5;
+        const EXPONENT: usize = 5;
+    }
+
+    macro_rules! mimc_mock {
+        ($name:ident, $exp:literal) => {
+            #[derive(Clone, Default)]
+            struct $name;
+
+            impl MiMCParameters for $name {
+                const ROUNDS: usize = 9;
+                const EXPONENT: usize = $exp;
+            }
+        };
+    }
+
+    mimc_mock!(MiMCMock3, 3);
+    mimc_mock!(MiMCMock7, 7);
+    mimc_mock!(MiMCMock11, 11);
+
+    /// Round function as originally written, one multiplication per exponent
+    fn reference_feistel<P: MiMCParameters>(mimc: &MiMC<Fr, P>, mut x_l: Fr, mut x_r: Fr) -> (Fr, Fr) {
         for i in 0..P::ROUNDS {
             let t = match i == 0 {
-                true => self.k + x_l,
-                false => self.k + x_l + self.round_keys[i],
+                true => mimc.k + x_l,
+                false => mimc.k + x_l + mimc.round_keys[i],
             };
-            let mut tn = F::one();
+            let mut tn = Fr::one();
             (0..P::EXPONENT).for_each(|_| tn *= t);
             (x_l, x_r) = match i < P::ROUNDS - 1 {
                 true => (x_r + tn, x_l),
@@ -79,36 +467,112 @@ impl<F: PrimeField, P: MiMCParameters> MiMC<F, P> {
         (x_l, x_r)
     }
 
-    /// MiMC n/n x^exp permute
-    pub fn permute_non_feistel(&self, state: Vec<F>) -> Vec<F> {
-        let mut r = self.k;
-        for s in state.into_iter() {
-            r += s + self.non_feistel(s, r);
-        }
-        let mut outputs = vec![r];
-        match self.num_outputs {
-            0 | 1 => outputs,
-            _ => {
-                for _ in 1..self.num_outputs {
-                    r += self.non_feistel(r, r);
-                    outputs.push(r);
-                }
-                outputs
-            }
-        }
-    }
-
-    fn non_feistel(&self, x: F, k: F) -> F {
-        let mut r = F::zero();
+    fn reference_non_feistel<P: MiMCParameters>(mimc: &MiMC<Fr, P>, x: Fr, k: Fr) -> Fr {
+        let mut r = Fr::zero();
         for i in 0..P::ROUNDS {
             let t = match i == 0 {
                 true => k + x,
-                false => k + r + self.round_keys[i],
+                false => k + r + mimc.round_keys[i],
             };
-            let mut tn = F::one();
-            (0..P::EXPONENT).for_each(|_| tn *= t);
-            r = tn;
+            r = t.pow([P::EXPONENT as u64]);
         }
         r + k
     }
+
+    fn check_against_reference<P: MiMCParameters>() {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, P>::rand_unvalidated(rng);
+        let (x, y) = (Fr::rand(rng), Fr::rand(rng));
+
+        let (mut r, mut c) = reference_feistel(&mimc, x, Fr::zero());
+        r += y;
+        (r, _) = reference_feistel(&mimc, r, c);
+        assert_eq!(mimc.permute_feistel(vec![x, y]), vec![r]);
+
+        r = mimc.k;
+        r += x + reference_non_feistel(&mimc, x, r);
+        r += y + reference_non_feistel(&mimc, y, r);
+        assert_eq!(mimc.permute_non_feistel(vec![x, y]), vec![r]);
+
+        c = mimc.k + Fr::one();
+        let changed = MiMC::<Fr, P>::try_new_unvalidated(1, c, mimc.round_keys.clone()).unwrap();
+        assert_eq!(changed.feistel(x, y), reference_feistel(&changed, x, y));
+
+        // Setters rebuild the round constants used by the feistel rounds
+        let mut updated = mimc.clone();
+        updated.set_k(c);
+        for permutation_type in [PermutationType::Feistel, PermutationType::NonFeistel] {
+            assert_eq!(
+                updated.permute(permutation_type, vec![x, y]),
+                changed.permute(permutation_type, vec![x, y])
+            );
+        }
+        let round_keys = (0..P::ROUNDS).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        updated.set_round_keys(round_keys.clone()).unwrap();
+        let changed = MiMC::<Fr, P>::try_new_unvalidated(1, c, round_keys).unwrap();
+        assert_eq!(updated.feistel(x, y), reference_feistel(&changed, x, y));
+        assert!(updated.set_round_keys(vec![]).is_err());
+        assert_eq!(updated.round_keys(), changed.round_keys());
+    }
+
+    #[test]
+    fn errors_instead_of_panics() {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+
+        assert_eq!(
+            MiMC::<Fr, MiMCMock>::try_new(1, mimc.k, mimc.round_keys.clone()),
+            Err(MiMCError::ExponentNotPermutation(5))
+        );
+        assert_eq!(
+            MiMC::<Fr, MiMCMock>::try_new_unvalidated(1, mimc.k, mimc.round_keys[1..].to_vec()),
+            Err(MiMCError::InvalidRoundKeysLength {
+                expected: 5,
+                actual: 4
+            })
+        );
+        assert!(
+            <MiMCNonFeistelCRH<Fr, MiMCMock> as TwoToOneCRHScheme>::evaluate(
+                &mimc,
+                [1u8, 2].as_slice(),
+                [3u8].as_slice(),
+            )
+            .is_err()
+        );
+        assert_eq!(
+            round_keys_contants_to_vec::<Fr>(&["0", "1", "x"]),
+            Err(MiMCError::InvalidRoundKey {
+                index: 2,
+                value: "x".to_string()
+            })
+        );
+        assert_eq!(
+            mimc.compress_level(PermutationType::Feistel, &[Fr::one()]),
+            Err(MiMCError::OddTreeLevel(1))
+        );
+    }
+
+    #[test]
+    fn permutation_matches_reference() {
+        check_against_reference::<MiMCMock3>();
+        check_against_reference::<MiMCMock>();
+        check_against_reference::<MiMCMock7>();
+        check_against_reference::<MiMCMock11>();
+    }
+
+    #[test]
+    fn hash_many_matches_sequential() {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        let inputs = (0..64)
+            .map(|i| (0..i % 4 + 1).map(|_| Fr::rand(rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        for permutation_type in [PermutationType::Feistel, PermutationType::NonFeistel] {
+            let hashed = mimc.hash_many(permutation_type, &inputs);
+            for (input, output) in inputs.iter().zip(hashed) {
+                assert_eq!(mimc.permute(permutation_type, input.clone()), output);
+            }
+        }
+    }
 }
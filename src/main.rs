@@ -1,7 +0,0 @@
-mod traits;
-mod constraints;
-mod utils;
-mod paramgen;
-mod params;
-
-fn main() {}
\ No newline at end of file
@@ -0,0 +1,220 @@
+use ark_ff::PrimeField;
+
+use crate::{DynMiMC, MiMC, MiMCError, MiMCParameters, PermutationType};
+
+/// Largest depth of a [`MerkleTree`], which stores all `2^depth` leaves
+pub const MAX_DEPTH: usize = 32;
+
+/// Native binary Merkle tree whose inner nodes are MiMC two-to-one compressions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree<F: PrimeField> {
+    pub permutation_type: PermutationType,
+    /// Levels from the (zero padded) leaves up to the root
+    pub levels: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MerkleTree<F> {
+    /// Build a tree of `2^depth` leaves, padding `leaves` with zeros. Fails above
+    /// [`MAX_DEPTH`] or where `2^depth` overflows `usize`. Each level is compressed in parallel with the `parallel` feature.
+    pub fn new<P: MiMCParameters>(
+        mimc: &MiMC<F, P>,
+        permutation_type: PermutationType,
+        depth: usize,
+        leaves: &[F],
+    ) -> Result<Self, MiMCError> {
+        Self::build(permutation_type, depth, leaves, |nodes| {
+            mimc.compress_level(permutation_type, nodes)
+        })
+    }
+
+    /// [`MerkleTree::new`] with the permutation type of `mimc`
+    pub fn new_dyn(mimc: &DynMiMC<F>, depth: usize, leaves: &[F]) -> Result<Self, MiMCError> {
+        Self::build(mimc.permutation_type, depth, leaves, |nodes| {
+            mimc.compress_level(nodes)
+        })
+    }
+
+    fn build(
+        permutation_type: PermutationType,
+        depth: usize,
+        leaves: &[F],
+        compress_level: impl Fn(&[F]) -> Result<Vec<F>, MiMCError>,
+    ) -> Result<Self, MiMCError> {
+        let size = match depth <= MAX_DEPTH {
+            true => 1usize.checked_shl(depth as u32),
+            false => None,
+        }
+        .ok_or(MiMCError::TreeTooDeep {
+            depth,
+            maximum: MAX_DEPTH,
+        })?;
+        if leaves.len() > size {
+            return Err(MiMCError::TooManyLeaves {
+                depth,
+                leaves: leaves.len(),
+            });
+        }
+        let mut level = leaves.to_vec();
+        level.resize(size, F::zero());
+        let mut levels = vec![level];
+        for _ in 0..depth {
+            let next = compress_level(&levels[levels.len() - 1])?;
+            levels.push(next);
+        }
+        Ok(Self {
+            permutation_type,
+            levels,
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn leaves(&self) -> &[F] {
+        &self.levels[0]
+    }
+
+    pub fn root(&self) -> F {
+        self.levels[self.depth()][0]
+    }
+
+    /// Inclusion proof of the leaf at `index`, which may be a padding leaf
+    pub fn proof(&self, index: usize) -> Result<MerkleProof<F>, MiMCError> {
+        let leaves = self.leaves();
+        if index >= leaves.len() {
+            return Err(MiMCError::LeafIndexOutOfRange {
+                index,
+                leaves: leaves.len(),
+            });
+        }
+        Ok(MerkleProof {
+            index,
+            leaf: leaves[index],
+            siblings: self.levels[..self.depth()]
+                .iter()
+                .enumerate()
+                .map(|(height, level)| level[(index >> height) ^ 1])
+                .collect(),
+        })
+    }
+}
+
+/// Path from a leaf to the root, with the bits of `index` from the lowest up
+/// telling whether the node at each height is a right child
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof<F: PrimeField> {
+    pub index: usize,
+    pub leaf: F,
+    /// Sibling at each height, from the leaves up
+    pub siblings: Vec<F>,
+}
+
+impl<F: PrimeField> MerkleProof<F> {
+    /// Whether the proof leads to `root` in a tree built with [`MerkleTree::new`]
+    pub fn verify<P: MiMCParameters>(
+        &self,
+        mimc: &MiMC<F, P>,
+        permutation_type: PermutationType,
+        root: F,
+    ) -> bool {
+        self.root_with(|left, right| mimc.compress(permutation_type, left, right)) == Some(root)
+    }
+
+    /// Whether the proof leads to `root` in a tree built with [`MerkleTree::new_dyn`]
+    pub fn verify_dyn(&self, mimc: &DynMiMC<F>, root: F) -> bool {
+        self.root_with(|left, right| mimc.compress(left, right)) == Some(root)
+    }
+
+    /// `None` if `index` does not fit in the depth
+    fn root_with(&self, compress: impl Fn(F, F) -> F) -> Option<F> {
+        let bit = |height: usize| self.index.checked_shr(height as u32).unwrap_or(0) & 1;
+        if self.index.checked_shr(self.siblings.len() as u32).unwrap_or(0) != 0 {
+            return None;
+        }
+        Some(
+            self.siblings
+                .iter()
+                .enumerate()
+                .fold(self.leaf, |node, (height, sibling)| match bit(height) {
+                    0 => compress(node, *sibling),
+                    _ => compress(*sibling, node),
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_377::Fr;
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{MiMC, MiMCError, MiMCParameters, PermutationType};
+
+    use super::{MerkleTree, MAX_DEPTH};
+
+    #[derive(Clone, Default)]
+    struct MiMCMock;
+
+    impl MiMCParameters for MiMCMock {
+        const ROUNDS: usize = 5;
+        const EXPONENT: usize = 5;
+    }
+
+    #[test]
+    fn root_matches_sequential() {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        let leaves = (0..13).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let tree = MerkleTree::new(&mimc, PermutationType::Feistel, 4, &leaves).unwrap();
+        assert!(MerkleTree::new(&mimc, PermutationType::Feistel, 3, &leaves).is_err());
+        for depth in [MAX_DEPTH + 1, 64, usize::MAX] {
+            assert_eq!(
+                MerkleTree::new(&mimc, PermutationType::Feistel, depth, &leaves),
+                Err(MiMCError::TreeTooDeep {
+                    depth,
+                    maximum: MAX_DEPTH
+                })
+            );
+        }
+
+        let mut level = leaves.clone();
+        level.resize(16, Fr::from(0));
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| mimc.permute_feistel(vec![pair[0], pair[1]])[0])
+                .collect();
+        }
+        assert_eq!(tree.depth(), 4);
+        assert_eq!(tree.root(), level[0]);
+    }
+
+    #[test]
+    fn proofs() {
+        let rng = &mut test_rng();
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(rng);
+        let leaves = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let tree = MerkleTree::new(&mimc, PermutationType::NonFeistel, 3, &leaves).unwrap();
+        let dyn_mimc = mimc.to_dyn(PermutationType::NonFeistel);
+        assert_eq!(MerkleTree::new_dyn(&dyn_mimc, 3, &leaves).unwrap(), tree);
+
+        for index in 0..8 {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.leaf, tree.leaves()[index]);
+            assert!(proof.verify(&mimc, PermutationType::NonFeistel, tree.root()));
+            assert!(proof.verify_dyn(&dyn_mimc, tree.root()));
+            assert!(!proof.verify(&mimc, PermutationType::Feistel, tree.root()));
+        }
+        assert_eq!(
+            tree.proof(8),
+            Err(MiMCError::LeafIndexOutOfRange { index: 8, leaves: 8 })
+        );
+
+        let mut proof = tree.proof(2).unwrap();
+        proof.index = 3;
+        assert!(!proof.verify_dyn(&dyn_mimc, tree.root()));
+        proof.index = 10;
+        assert!(!proof.verify_dyn(&dyn_mimc, tree.root()));
+    }
+}
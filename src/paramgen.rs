@@ -1,7 +1,12 @@
 // use ark_ff::{FpParameters, PrimeField};
 use ark_ff::PrimeField;
-use rug::{Assign, Float};
-use tiny_keccak::{Hasher, Keccak};
+use tiny_keccak::{Hasher, Keccak, Sha3, Shake, Xof};
+
+pub use crate::PermutationType;
+use crate::{
+    validation::{is_permutation, min_rounds, rounds_to_reach},
+    DynMiMC, MiMCError,
+};
 
 fn hash_keccak(bytes: &[u8]) -> Vec<u8> {
     let mut hasher = Keccak::v256();
@@ -11,82 +16,232 @@ fn hash_keccak(bytes: &[u8]) -> Vec<u8> {
     output
 }
 
-fn round_keys_length<F: PrimeField>(permutation_type: PermutationType, exponent: usize) -> usize {
-    let mut modulus = Float::new(20);
-    modulus
-        // .assign(Float::parse_radix(<F::Params as FpParameters>::MODULUS.to_string(), 16).unwrap());
-        .assign(Float::parse_radix(<F as PrimeField>::MODULUS.to_string(), 16).unwrap());
-    let mut div = Float::new(20);
-    div.assign(Float::parse(exponent.to_string()).unwrap());
-    modulus.log10_mut();
-    div.log10_mut();
-    let len = (modulus / div).ceil().to_u32_saturating().unwrap() as usize;
-    match permutation_type {
-        PermutationType::Feistel => len * 2,
-        PermutationType::NonFeistel => len,
-    }
+fn hash_sha3(bytes: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha3::v256();
+    let mut output = vec![0u8; 32];
+    hasher.update(bytes);
+    hasher.finalize(&mut output);
+    output
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub enum PermutationType {
-    Feistel,
-    #[default]
-    NonFeistel,
+/// Smallest `r` with `exponent^r >= p`, computed exactly on `F::MODULUS`,
+/// doubled for feistel since each round only permutes half the state
+fn round_keys_length<F: PrimeField>(
+    permutation_type: PermutationType,
+    exponent: usize,
+) -> Result<usize, MiMCError> {
+    let len = min_rounds::<F>(exponent)?;
+    Ok(match permutation_type {
+        PermutationType::Feistel => len * 2,
+        PermutationType::NonFeistel => len,
+    })
 }
 
 #[inline]
 pub fn generate_default_round_keys<F: PrimeField>(
     permutation_type: PermutationType,
     exponent: usize,
-) -> (usize, Vec<F>) {
-    generate_round_keys(
+) -> Result<(usize, Vec<F>), MiMCError> {
+    generate_round_keys(permutation_type, exponent, default_seed(permutation_type))
+}
+
+/// circomlib's seeds, `mimcsponge` for feistel and `mimc` otherwise
+fn default_seed(permutation_type: PermutationType) -> &'static [u8] {
+    match permutation_type {
+        PermutationType::Feistel => b"mimcsponge",
+        PermutationType::NonFeistel => b"mimc",
+    }
+}
+
+pub fn generate_round_keys<F: PrimeField>(
+    permutation_type: PermutationType,
+    exponent: usize,
+    seed: &[u8],
+) -> Result<(usize, Vec<F>), MiMCError> {
+    generate_round_keys_with(
         permutation_type,
         exponent,
-        match permutation_type {
-            PermutationType::Feistel => b"mimcsponge",
-            PermutationType::NonFeistel => b"mimc",
-        },
+        seed,
+        &ConstantDerivation::CircomlibKeccak,
     )
 }
 
-pub fn generate_round_keys<F: PrimeField>(
+/// [`generate_round_keys`] with the round keys derived by `derivation`
+pub fn generate_round_keys_with<F: PrimeField>(
     permutation_type: PermutationType,
     exponent: usize,
     seed: &[u8],
-) -> (usize, Vec<F>) {
-    let round_keys_length = round_keys_length::<F>(permutation_type, exponent);
-    let mut rounds: Vec<F> = vec![];
-    let mut c = seed.to_vec();
-    for _ in 0..round_keys_length {
-        c = hash_keccak(&c);
-        let f = F::from_be_bytes_mod_order(&c);
-        rounds.push(f);
+    derivation: &ConstantDerivation<F>,
+) -> Result<(usize, Vec<F>), MiMCError> {
+    let round_keys_length = round_keys_length::<F>(permutation_type, exponent)?;
+    Ok((
+        round_keys_length,
+        derivation.derive(permutation_type, round_keys_length, seed),
+    ))
+}
+
+/// `rounds` round keys from iterated Keccak-256 of `seed`, with the first round key
+/// and, for feistel, the last one zeroed. [`crate::mimc_preset`] derives the same
+/// keys at compile time.
+pub fn derive_round_keys<F: PrimeField>(
+    permutation_type: PermutationType,
+    rounds: usize,
+    seed: &[u8],
+) -> Vec<F> {
+    ConstantDerivation::CircomlibKeccak.derive(permutation_type, rounds, seed)
+}
+
+/// Extra rounds [`recommend`] adds on top of the attack bounds, in percent
+pub const SECURITY_MARGIN_PERCENT: usize = 10;
+
+/// Parameters for any `F` with the smallest odd prime exponent `e` such that `x^e`
+/// permutes `F`, and enough rounds for both `e^r >= p` and `e^r >= 2^security_bits`,
+/// plus [`SECURITY_MARGIN_PERCENT`] rounded up, doubled for feistel. The round keys
+/// are derived as in [`generate_default_round_keys`], with key zero and one output.
+pub fn recommend<F: PrimeField>(
+    permutation_type: PermutationType,
+    security_bits: usize,
+) -> Result<DynMiMC<F>, MiMCError> {
+    let maximum = F::MODULUS_BIT_SIZE as usize;
+    if security_bits > maximum {
+        return Err(MiMCError::SecurityLevelTooHigh {
+            bits: security_bits,
+            maximum,
+        });
     }
-    match permutation_type {
-        PermutationType::Feistel => {
-            rounds[round_keys_length - 1] = F::zero();
-            rounds[0] = F::zero();
+    // Terminates, since `p - 1` has finitely many prime factors
+    let exponent = (3usize..)
+        .step_by(2)
+        .filter(|e| (3..*e).step_by(2).take_while(|d| d * d <= *e).all(|d| e % d != 0))
+        .find(|e| is_permutation::<F>(*e))
+        .unwrap();
+
+    let mut security_bound = vec![0u64; security_bits / 64 + 1];
+    security_bound[security_bits / 64] = 1 << (security_bits % 64);
+    let rounds = min_rounds::<F>(exponent)?.max(rounds_to_reach(exponent, &security_bound)?);
+    let rounds = rounds + (rounds * SECURITY_MARGIN_PERCENT).div_ceil(100);
+    let rounds = match permutation_type {
+        PermutationType::Feistel => rounds * 2,
+        PermutationType::NonFeistel => rounds,
+    };
+
+    DynMiMC::try_new(
+        permutation_type,
+        exponent,
+        1,
+        F::zero(),
+        derive_round_keys(permutation_type, rounds, default_seed(permutation_type)),
+    )
+}
+
+/// How round keys are derived from a seed. Each scheme follows the generator of one
+/// implementation, including which round keys it zeroes and the byte order digests
+/// are reduced with, so constants from that ecosystem can be regenerated and checked.
+///
+/// The round functions of this crate never read the first round key, and
+/// [`crate::validate_round_keys`] requires it to be zero.
+#[derive(Clone, Copy)]
+pub enum ConstantDerivation<'a, F> {
+    /// circomlib's `mimc7.js` and `mimcsponge.js`: round key `i` is the 32-byte
+    /// Keccak-256 digest iterated `i + 1` times over the seed, reduced big-endian.
+    /// The first round key is zeroed, and for feistel the last one too.
+    CircomlibKeccak,
+    /// gnark-crypto's chain with SHA3-256: the seed is hashed once before the
+    /// first round key, then round key `i` is the next digest reduced big-endian.
+    /// No round key is zeroed.
+    GnarkSha3,
+    /// go-iden3-crypto's `mimc7`: the circomlib chain, but each digest is hashed
+    /// again as a minimal big-endian integer, so its leading zero bytes are dropped.
+    /// Only the first round key is zeroed, whatever the permutation type.
+    Iden3Mimc7,
+    /// One SHAKE256 stream over the seed, squeezing 16 bytes more than the modulus
+    /// per round key and reducing them little-endian to keep the bias negligible.
+    /// The first round key is zeroed, and for feistel the last one too.
+    ShakeXof,
+    /// `(seed, rounds)` to the round keys, which are used as returned
+    Custom(&'a dyn Fn(&[u8], usize) -> Vec<F>),
+}
+
+impl<'a, F: PrimeField> ConstantDerivation<'a, F> {
+    pub fn derive(&self, permutation_type: PermutationType, rounds: usize, seed: &[u8]) -> Vec<F> {
+        let mut round_keys: Vec<F> = match self {
+            Self::CircomlibKeccak => {
+                let mut c = seed.to_vec();
+                (0..rounds)
+                    .map(|_| {
+                        c = hash_keccak(&c);
+                        F::from_be_bytes_mod_order(&c)
+                    })
+                    .collect()
+            }
+            Self::GnarkSha3 => {
+                let mut c = hash_sha3(seed);
+                (0..rounds)
+                    .map(|_| {
+                        c = hash_sha3(&c);
+                        F::from_be_bytes_mod_order(&c)
+                    })
+                    .collect()
+            }
+            Self::Iden3Mimc7 => {
+                let mut c = seed.to_vec();
+                (0..rounds)
+                    .map(|i| {
+                        // `big.Int.Bytes()` of the previous digest, except for the seed
+                        let start = match i {
+                            0 => 0,
+                            _ => c.iter().take_while(|b| **b == 0).count(),
+                        };
+                        c = hash_keccak(&c[start..]);
+                        F::from_be_bytes_mod_order(&c)
+                    })
+                    .collect()
+            }
+            Self::ShakeXof => {
+                let mut shake = Shake::v256();
+                shake.update(seed);
+                let mut c = vec![0u8; (F::MODULUS_BIT_SIZE as usize).div_ceil(8) + 16];
+                (0..rounds)
+                    .map(|_| {
+                        shake.squeeze(&mut c);
+                        F::from_le_bytes_mod_order(&c)
+                    })
+                    .collect()
+            }
+            Self::Custom(derive) => return derive(seed, rounds),
+        };
+        if let Some(first) = round_keys.first_mut() {
+            if !matches!(self, Self::GnarkSha3) {
+                *first = F::zero();
+            }
         }
-        _ => {
-            rounds[0] = F::zero();
+        if let (PermutationType::Feistel, Some(last)) = (permutation_type, round_keys.last_mut()) {
+            if matches!(self, Self::CircomlibKeccak | Self::ShakeXof) {
+                *last = F::zero();
+            }
         }
-    };
-    (round_keys_length, rounds)
+        round_keys
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use ark_bls12_377::Fr;
-    use ark_ff::Zero;
+    use ark_bn254::Fr;
+    use ark_ff::{PrimeField, Zero};
 
-    use super::generate_default_round_keys;
+    use super::{
+        derive_round_keys, generate_default_round_keys, generate_round_keys_with, hash_sha3,
+        recommend, ConstantDerivation, PermutationType,
+    };
+    use crate::{validate_parameters, MiMCError};
 
     #[test]
     fn correct_keys() {
+        // circomlib's mimcsponge constants, generated for BN254
         let (length, rounds) =
-            generate_default_round_keys::<Fr>(super::PermutationType::Feistel, 5);
+            generate_default_round_keys::<Fr>(PermutationType::Feistel, 5).unwrap();
         assert_eq!(length, 220);
         assert_eq!(rounds.last().unwrap(), &Fr::zero());
         assert_eq!(rounds.first().unwrap(), &Fr::zero());
@@ -98,4 +253,84 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn exact_round_count() {
+        // 5^109 >= p > 5^108 for the 253-bit BLS12-377 scalar field
+        let (length, _) = generate_default_round_keys::<ark_bls12_377::Fr>(
+            PermutationType::Feistel,
+            5,
+        )
+        .unwrap();
+        assert_eq!(length, 218);
+        let (length, _) =
+            generate_default_round_keys::<Fr>(PermutationType::NonFeistel, 7).unwrap();
+        assert_eq!(length, 91);
+    }
+
+    #[test]
+    fn derivation_schemes() {
+        let derive = |derivation: ConstantDerivation<Fr>, permutation_type, rounds| {
+            derivation.derive(permutation_type, rounds, b"mimc")
+        };
+        let circomlib = derive(ConstantDerivation::CircomlibKeccak, PermutationType::NonFeistel, 160);
+        #[cfg(feature = "mimc-7-91-bn254")]
+        assert_eq!(
+            circomlib[..91],
+            crate::params::mimc_7_91_bn254::MIMC_7_91_BN254_ROUND_KEYS
+        );
+
+        // The 153rd Keccak digest of "mimc" starts with a zero byte, which iden3 drops
+        // before hashing it again
+        let iden3 = derive(ConstantDerivation::Iden3Mimc7, PermutationType::Feistel, 160);
+        assert_eq!(circomlib.iter().zip(&iden3).position(|(a, b)| a != b), Some(153));
+        assert_ne!(iden3.last().unwrap(), &Fr::zero());
+
+        let gnark = derive(ConstantDerivation::GnarkSha3, PermutationType::Feistel, 91);
+        assert_eq!(gnark[0], Fr::from_be_bytes_mod_order(&hash_sha3(&hash_sha3(b"mimc"))));
+        assert!(gnark.iter().all(|key| !key.is_zero()));
+
+        let shake = derive(ConstantDerivation::ShakeXof, PermutationType::Feistel, 220);
+        assert!(shake[0].is_zero() && shake[219].is_zero());
+        assert!(shake[1..219].iter().all(|key| !key.is_zero()));
+        assert_eq!(
+            shake[..91],
+            derive(ConstantDerivation::ShakeXof, PermutationType::NonFeistel, 91)
+        );
+
+        let custom = |_: &[u8], rounds| (0..rounds as u64).map(Fr::from).collect();
+        let (length, round_keys) = generate_round_keys_with(
+            PermutationType::NonFeistel,
+            7,
+            b"",
+            &ConstantDerivation::Custom(&custom),
+        )
+        .unwrap();
+        assert_eq!(round_keys, (0..length as u64).map(Fr::from).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn recommended_parameters() {
+        // 3 divides p - 1, ceil(log_5 p) = 110 plus 10%
+        let mimc = recommend::<Fr>(PermutationType::NonFeistel, 128).unwrap();
+        assert_eq!((mimc.exponent(), mimc.rounds()), (5, 121));
+        assert_eq!(mimc.round_keys(), derive_round_keys(PermutationType::NonFeistel, 121, b"mimc"));
+        let mimc = recommend::<Fr>(PermutationType::Feistel, 128).unwrap();
+        assert_eq!((mimc.exponent(), mimc.rounds()), (5, 242));
+
+        // 3, 5 and 7 all divide p - 1
+        let mimc = recommend::<ark_bls12_377::Fr>(PermutationType::NonFeistel, 128).unwrap();
+        assert_eq!((mimc.exponent(), mimc.rounds()), (11, 81));
+        assert!(validate_parameters::<ark_bls12_377::Fr>(mimc.exponent(), mimc.rounds()).is_ok());
+
+        // 5^110 >= 2^254 already, and nothing above the field size is reachable
+        assert_eq!(recommend::<Fr>(PermutationType::NonFeistel, 254).unwrap().rounds(), 121);
+        assert_eq!(
+            recommend::<Fr>(PermutationType::NonFeistel, 255),
+            Err(MiMCError::SecurityLevelTooHigh {
+                bits: 255,
+                maximum: 254
+            })
+        );
+    }
 }
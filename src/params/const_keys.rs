@@ -0,0 +1,301 @@
+//! Compile-time round keys for [`crate::mimc_preset`]: the
+//! [`crate::paramgen::derive_round_keys`] Keccak chain and the parameter checks of
+//! [`crate::validate_parameters`], written as `const fn` so a preset's table is a
+//! constant. Only fields with a Montgomery backend are supported, which covers
+//! every arkworks curve field.
+
+use ark_ff::{BigInt, Fp, MontBackend, MontConfig};
+
+const KECCAK_RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+const fn keccak_f(a: &mut [u64; 25]) {
+    let mut round = 0;
+    while round < 24 {
+        // theta
+        let mut c = [0u64; 5];
+        let mut x = 0;
+        while x < 5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            x += 1;
+        }
+        x = 0;
+        while x < 5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            let mut y = 0;
+            while y < 25 {
+                a[x + y] ^= d;
+                y += 5;
+            }
+            x += 1;
+        }
+        // rho and pi
+        let mut last = a[1];
+        let mut i = 0;
+        while i < 24 {
+            let lane = a[PI[i]];
+            a[PI[i]] = last.rotate_left(RHO[i]);
+            last = lane;
+            i += 1;
+        }
+        // chi
+        let mut y = 0;
+        while y < 25 {
+            let row = [a[y], a[y + 1], a[y + 2], a[y + 3], a[y + 4]];
+            x = 0;
+            while x < 5 {
+                a[y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+                x += 1;
+            }
+            y += 5;
+        }
+        // iota
+        a[0] ^= ROUND_CONSTANTS[round];
+        round += 1;
+    }
+}
+
+/// Keccak-256 with the original `0x01` padding, as `tiny_keccak::Keccak::v256`
+const fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut state = [0u64; 25];
+    let mut block = [0u8; KECCAK_RATE];
+    let mut offset = 0;
+    loop {
+        let remaining = input.len() - offset;
+        let length = if remaining < KECCAK_RATE { remaining } else { KECCAK_RATE };
+        let mut i = 0;
+        while i < KECCAK_RATE {
+            block[i] = if i < length { input[offset + i] } else { 0 };
+            i += 1;
+        }
+        if length < KECCAK_RATE {
+            block[length] ^= 0x01;
+            block[KECCAK_RATE - 1] ^= 0x80;
+        }
+        i = 0;
+        while i < KECCAK_RATE / 8 {
+            let mut lane = [0u8; 8];
+            let mut j = 0;
+            while j < 8 {
+                lane[j] = block[8 * i + j];
+                j += 1;
+            }
+            state[i] ^= u64::from_le_bytes(lane);
+            i += 1;
+        }
+        keccak_f(&mut state);
+        offset += length;
+        if length < KECCAK_RATE {
+            break;
+        }
+    }
+    let mut digest = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        digest[i] = state[i / 8].to_le_bytes()[i % 8];
+        i += 1;
+    }
+    digest
+}
+
+/// `a >= b` on little-endian 64-bit limbs
+const fn ge<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+/// `a - b` on little-endian 64-bit limbs, wrapping
+const fn sub<const N: usize>(a: &mut [u64; N], b: &[u64; N]) {
+    let mut borrow = false;
+    let mut i = 0;
+    while i < N {
+        let (difference, underflow) = a[i].overflowing_sub(b[i]);
+        let (difference, underflow_borrow) = difference.overflowing_sub(borrow as u64);
+        a[i] = difference;
+        borrow = underflow || underflow_borrow;
+        i += 1;
+    }
+}
+
+/// `bytes` read big-endian and reduced modulo `modulus`, as `from_be_bytes_mod_order`
+const fn reduce_be<const N: usize>(bytes: &[u8; 32], modulus: &[u64; N]) -> [u64; N] {
+    let mut r = [0u64; N];
+    let mut bit = 0;
+    while bit < 256 {
+        // `r = 2r + b`, below `2p` since `r < p`
+        let mut carry = ((bytes[bit / 8] >> (7 - bit % 8)) & 1) as u64;
+        let mut i = 0;
+        while i < N {
+            let top = r[i] >> 63;
+            r[i] = (r[i] << 1) | carry;
+            carry = top;
+            i += 1;
+        }
+        if carry == 1 || ge(&r, modulus) {
+            sub(&mut r, modulus);
+        }
+        bit += 1;
+    }
+    r
+}
+
+/// Smallest `r` with `exponent^r >= modulus`, as `validation::min_rounds`
+const fn min_rounds<const N: usize>(exponent: u64, modulus: &[u64; N]) -> usize {
+    let mut power = [0u64; N];
+    power[0] = 1;
+    let mut rounds = 0;
+    let mut overflow = false;
+    while !overflow && !ge(&power, modulus) {
+        let mut carry = 0u128;
+        let mut i = 0;
+        while i < N {
+            let product = power[i] as u128 * exponent as u128 + carry;
+            power[i] = product as u64;
+            carry = product >> 64;
+            i += 1;
+        }
+        overflow = carry > 0;
+        rounds += 1;
+    }
+    rounds
+}
+
+/// `gcd(exponent, modulus - 1) = 1` for an odd modulus
+const fn is_permutation<const N: usize>(exponent: u64, modulus: &[u64; N]) -> bool {
+    let mut rem = 0u128;
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        let limb = if i == 0 { modulus[0] - 1 } else { modulus[i] };
+        rem = ((rem << 64) | limb as u128) % exponent as u128;
+    }
+    let (mut a, mut b) = (exponent as u128, rem);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a == 1
+}
+
+/// `R` round keys derived from `seed` as [`crate::paramgen::derive_round_keys`], after
+/// checking that `x^exponent` permutes the field and that `R` reaches `ceil(log_e p)`,
+/// doubled for feistel. A failed check is a compile error where the result is a constant.
+#[doc(hidden)]
+pub const fn round_keys<P: MontConfig<N>, const N: usize, const R: usize>(
+    exponent: usize,
+    feistel: bool,
+    seed: &[u8],
+) -> [Fp<MontBackend<P, N>, N>; R] {
+    let modulus = &P::MODULUS.0;
+    assert!(exponent >= 2, "mimc_preset!: the exponent must be at least 2");
+    assert!(
+        is_permutation(exponent as u64, modulus),
+        "mimc_preset!: x^exponent is not a permutation of the field"
+    );
+    let minimum = min_rounds(exponent as u64, modulus);
+    assert!(
+        R >= if feistel { 2 * minimum } else { minimum },
+        "mimc_preset!: fewer rounds than ceil(log_e p), doubled for feistel"
+    );
+
+    let zero = Fp::new(BigInt([0; N]));
+    let mut keys = [zero; R];
+    let mut c = keccak256(seed);
+    let mut i = 0;
+    while i < R {
+        if i > 0 {
+            c = keccak256(&c);
+            keys[i] = Fp::new(BigInt(reduce_be(&c, modulus)));
+        }
+        i += 1;
+    }
+    if feistel && R > 0 {
+        keys[R - 1] = zero;
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_377::{Fr, FrConfig};
+    use ark_ff::MontConfig;
+
+    use crate::validation::{is_permutation, min_rounds};
+
+    #[test]
+    #[cfg(feature = "paramgen")]
+    fn keccak_matches_runtime() {
+        use ark_ff::PrimeField;
+        use tiny_keccak::{Hasher, Keccak};
+
+        // Two blocks and a block boundary
+        for length in [0, 32, 135, 136, 300] {
+            let input = (0..length).map(|i| i as u8).collect::<Vec<_>>();
+            let mut expected = [0u8; 32];
+            let mut keccak = Keccak::v256();
+            keccak.update(&input);
+            keccak.finalize(&mut expected);
+            assert_eq!(super::keccak256(&input), expected);
+            assert_eq!(
+                super::reduce_be(&expected, &FrConfig::MODULUS.0),
+                Fr::from_be_bytes_mod_order(&expected).into_bigint().0
+            );
+        }
+    }
+
+    #[test]
+    fn matches_runtime() {
+        // (2^256 - 1) mod 101
+        assert_eq!(super::reduce_be(&[0xff; 32], &[101]), [36]);
+
+        for exponent in [3, 5, 7, 11, 13] {
+            assert_eq!(
+                super::min_rounds(exponent, &FrConfig::MODULUS.0),
+                min_rounds::<Fr>(exponent as usize).unwrap()
+            );
+            assert_eq!(
+                super::is_permutation(exponent, &FrConfig::MODULUS.0),
+                is_permutation::<Fr>(exponent as usize)
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::MiMCParameters;
+use ark_bls12_377::Fr;
+use ark_ff::MontFp;
+
+use crate::{params::MiMCPreset, MiMC, MiMCParameters, PermutationType};
 
 #[derive(Debug, Clone, Default)]
 pub struct MIMC_7_90_BLS12_377_PARAMS;
@@ -6,97 +9,115 @@ pub struct MIMC_7_90_BLS12_377_PARAMS;
 impl MiMCParameters for MIMC_7_90_BLS12_377_PARAMS {
     const ROUNDS: usize = 90;
     const EXPONENT: usize = 5;
+    const PRESET_ID: Option<&'static str> = Some(<Self as MiMCPreset>::NAME);
 }
 
-pub const MIMC_7_90_BLS12_377_ROUND_KEYS: [&str; MIMC_7_90_BLS12_377_PARAMS::ROUNDS] = [
-    "0",
-    "5554214908621940931229464129709931006765938749529786559064714728324498888928",
-    "8374841360543350637560013769754311657433088249117086436252512323399906874181",
-    "6443212247583844257225121893022211323681110771657388605324429412033787284179",
-    "2928501216016863824120326161408509635614751390243553401897870421188704375433",
-    "4552303930772672293633427717085447435403219365870302052688201626398454549460",
-    "5667255182169955406946270262540721761437972880836467654488661278166986941203",
-    "7744836218846916304320072704511145645610625572871127657630249508950898854883",
-    "2926178189791121435682720436284125612066471221279278575865161033989469112306",
-    "5140310765669018826654938086011570694596597785167066012568449100610060711183",
-    "2723071651759269200079301634907410171169143209536537197116373440243319240041",
-    "5866787490986574189113690884265665276795784765757869633937277610462720214634",
-    "6594876232642788978538618617330744177293715361922295830126352915440242944929",
-    "2632385916954580941368956176626336146806721642583847728103570779270161510514",
-    "7355984722640067284097664292598114990547462019571680413626072555031485990153",
-    "3038345959687306222311554078710114904130052392639281723007156246053495324142",
-    "6469872874223860416097776451660467550021007923581371857823491794267867309296",
-    "5773536491140576410491248181067846220433110575694955658713505286935209846513",
-    "2730212993970529487234895298055489618561930707399322084742384039212626558610",
-    "3200364113023983988923841052623033434722678463172204403609136210999690312606",
-    "2936520855321441392061521059641597209359936033297677875018473901325886907710",
-    "6368038014676331973440483134713545238359486087610577112845492343884661653074",
-    "7725128504922676593111988218885356473521051140649078287937837957953143733518",
-    "2420313048196782283269077029162229336342008468388159202031766961051598553530",
-    "8144688184105456542540371141246939355195275443061434015358994929929837894297",
-    "3110180204686229160418263417015395440561484582452410953769115420725332373841",
-    "1208174930020657134243801088435870083376660851951919103360652311198817624500",
-    "5041568767143886985999864450489507377920889593217185883896121644439991710520",
-    "8358383950514513638905507948475946998016840745496157823006442950191728679873",
-    "1767371416232493117288675548231582270803285609277938592307595412930015045725",
-    "5978135476500201188299632687978559418020302706405222437211266833034231714321",
-    "6032365105133504724925793806318578936233045029919447519826248813478479197288",
-    "244548628064522520315169993614764192801285630117426095338882315300865912450",
-    "3954981445679826662277763992993952252317424180731134513164251215781782087612",
-    "3298609616860550074452974771160051506772873997816802651544180658398384500854",
-    "4871235749213547212015082526351840868683914114830093798786345925545791508016",
-    "6739722627047123650704294650168547689199576889424317598327664349670094847386",
-    "5876711363797423006700770728750239257178767385678524411096723688694222842109",
-    "1828508406871005342661409281905458172565424998541771811353500227801056695980",
-    "1819392441547488964857358023235890837134194206550699800280458365154308847152",
-    "5103567991330963725424807719462564311292603341608759139580417688134325303427",
-    "3257630669990946257562457598448463540210441901997544468482046523406787694771",
-    "1019797520008928012221152500817143413747093639720684650889419138381051759115",
-    "7351274525730206229615554719790121907387719668850022300851843198578377524290",
-    "210758120052884544082317340905437153211954461754982092465790402213150488674",
-    "7062136597506543861362336005879553531607814619912894591389210173899917606440",
-    "4397182231955809136152941414425052163379453599193221684830490309870373934067",
-    "5822875227400645907492447815932656589708997946392304102364148609558607021533",
-    "212847557486917249849041068951486529613513276293244177119247738458015528622",
-    "4931555854634339620695500977248118168354670085944833196656405956817786072015",
-    "4977304909301750086545378098648682214183401499508419973444812284581507681933",
-    "1829645857879599710118729530881719466212267750211549450274626006647955865531",
-    "5194669995904656347813227135329946362866202035008612080679398960455468349856",
-    "152517851289441914339426895806560905925240494857575822709998217410990679443",
-    "801108984839830094666274785331245479261942065449924454877833057328477739939",
-    "7051000157526010943801165200498652232544642915491188576830596595536499002687",
-    "2216432659854733047132347621569505613620980842043977268828076165669557467682",
-    "4418800001734787524987251640050330040572100200707088907985010242893658291716",
-    "472257316771368502865069657152674192844006801340183418049264785940810093802",
-    "591898082059731926865322464669510694084176792554159747638643214876026497355",
-    "4614227581364195720072398759748052409929603551886029816710943423469798880620",
-    "508534288156068795436622693042689714391079994065364903486093384232898120576",
-    "7777922852316062996337157300331910646083702852714396780630056464388736150341",
-    "8341153485941974502078971724658458159765645137234916652153939169154893007862",
-    "8253244735092047732753530257261116539217826234659178651062317836911806165149",
-    "884677124397453365974332400839373604026149779625834449320852275576742732225",
-    "2684107652991839161764237584472196746416389385103613911197239820018868861803",
-    "3882961030227078620454856097073284859664098505176009242080729988555204950592",
-    "6044419018302492240744085068511860953270664370633074724873708288135312403196",
-    "7707390991445273714317147920182095549933675225130098697967669255400856317979",
-    "2392027692032432593956355349966935988122341485573052015805810314199321357112",
-    "6467815055021393437440285680589533803539971219750761917222330908605918256924",
-    "3311013572018025154105127816606052439819382046839164709544455826759555063580",
-    "1675091356440682342827457053739608090747178596352636495639331124481272463377",
-    "5817665832191137403701351752678775176802639635697383890552279107332502141218",
-    "1727448059073003880468010518669528903511390697624445265460309749663011836259",
-    "2785684388229367761168628424572829158367376779525797663885422293667570555316",
-    "6703925662351728291552237935336643942889509277609505206483006425478661049603",
-    "7266047169830996984726911203997753313893371383301030971807333671225925225958",
-    "5081014996586530692661844219623689331456833333397467026161065905573598714285",
-    "4504741217333445854148320251999917599928334590189046243424126867736146166954",
-    "178931496010875552615369881766963384213323205956653188767106908572469657715",
-    "8019328450283110455848283781235557070364611197505432624108449936906394923408",
-    "2362963352605826758690071458128520804513177249581901481674149578090678259761",
-    "582694425648975085227372258164849518853717975679841928722645225176825516088",
-    "8255709983053966483919045503511290051227301519901575242480346243934566794057",
-    "7336683028030699763785320831478469392067611606981435233403193028558393070821",
-    "7321070916726277901235321171814463296233413243426467305689155732571950048209",
-    "7594017890037021425366623750593200398174488805473151513558919864633711506220",
-    "3645142745426229530714966160640087056786140365605704369450590004211944512348",
+pub const MIMC_7_90_BLS12_377_ROUND_KEYS: [Fr; MIMC_7_90_BLS12_377_PARAMS::ROUNDS] = [
+    MontFp!("0"),
+    MontFp!("5554214908621940931229464129709931006765938749529786559064714728324498888928"),
+    MontFp!("8374841360543350637560013769754311657433088249117086436252512323399906874181"),
+    MontFp!("6443212247583844257225121893022211323681110771657388605324429412033787284179"),
+    MontFp!("2928501216016863824120326161408509635614751390243553401897870421188704375433"),
+    MontFp!("4552303930772672293633427717085447435403219365870302052688201626398454549460"),
+    MontFp!("5667255182169955406946270262540721761437972880836467654488661278166986941203"),
+    MontFp!("7744836218846916304320072704511145645610625572871127657630249508950898854883"),
+    MontFp!("2926178189791121435682720436284125612066471221279278575865161033989469112306"),
+    MontFp!("5140310765669018826654938086011570694596597785167066012568449100610060711183"),
+    MontFp!("2723071651759269200079301634907410171169143209536537197116373440243319240041"),
+    MontFp!("5866787490986574189113690884265665276795784765757869633937277610462720214634"),
+    MontFp!("6594876232642788978538618617330744177293715361922295830126352915440242944929"),
+    MontFp!("2632385916954580941368956176626336146806721642583847728103570779270161510514"),
+    MontFp!("7355984722640067284097664292598114990547462019571680413626072555031485990153"),
+    MontFp!("3038345959687306222311554078710114904130052392639281723007156246053495324142"),
+    MontFp!("6469872874223860416097776451660467550021007923581371857823491794267867309296"),
+    MontFp!("5773536491140576410491248181067846220433110575694955658713505286935209846513"),
+    MontFp!("2730212993970529487234895298055489618561930707399322084742384039212626558610"),
+    MontFp!("3200364113023983988923841052623033434722678463172204403609136210999690312606"),
+    MontFp!("2936520855321441392061521059641597209359936033297677875018473901325886907710"),
+    MontFp!("6368038014676331973440483134713545238359486087610577112845492343884661653074"),
+    MontFp!("7725128504922676593111988218885356473521051140649078287937837957953143733518"),
+    MontFp!("2420313048196782283269077029162229336342008468388159202031766961051598553530"),
+    MontFp!("8144688184105456542540371141246939355195275443061434015358994929929837894297"),
+    MontFp!("3110180204686229160418263417015395440561484582452410953769115420725332373841"),
+    MontFp!("1208174930020657134243801088435870083376660851951919103360652311198817624500"),
+    MontFp!("5041568767143886985999864450489507377920889593217185883896121644439991710520"),
+    MontFp!("8358383950514513638905507948475946998016840745496157823006442950191728679873"),
+    MontFp!("1767371416232493117288675548231582270803285609277938592307595412930015045725"),
+    MontFp!("5978135476500201188299632687978559418020302706405222437211266833034231714321"),
+    MontFp!("6032365105133504724925793806318578936233045029919447519826248813478479197288"),
+    MontFp!("244548628064522520315169993614764192801285630117426095338882315300865912450"),
+    MontFp!("3954981445679826662277763992993952252317424180731134513164251215781782087612"),
+    MontFp!("3298609616860550074452974771160051506772873997816802651544180658398384500854"),
+    MontFp!("4871235749213547212015082526351840868683914114830093798786345925545791508016"),
+    MontFp!("6739722627047123650704294650168547689199576889424317598327664349670094847386"),
+    MontFp!("5876711363797423006700770728750239257178767385678524411096723688694222842109"),
+    MontFp!("1828508406871005342661409281905458172565424998541771811353500227801056695980"),
+    MontFp!("1819392441547488964857358023235890837134194206550699800280458365154308847152"),
+    MontFp!("5103567991330963725424807719462564311292603341608759139580417688134325303427"),
+    MontFp!("3257630669990946257562457598448463540210441901997544468482046523406787694771"),
+    MontFp!("1019797520008928012221152500817143413747093639720684650889419138381051759115"),
+    MontFp!("7351274525730206229615554719790121907387719668850022300851843198578377524290"),
+    MontFp!("210758120052884544082317340905437153211954461754982092465790402213150488674"),
+    MontFp!("7062136597506543861362336005879553531607814619912894591389210173899917606440"),
+    MontFp!("4397182231955809136152941414425052163379453599193221684830490309870373934067"),
+    MontFp!("5822875227400645907492447815932656589708997946392304102364148609558607021533"),
+    MontFp!("212847557486917249849041068951486529613513276293244177119247738458015528622"),
+    MontFp!("4931555854634339620695500977248118168354670085944833196656405956817786072015"),
+    MontFp!("4977304909301750086545378098648682214183401499508419973444812284581507681933"),
+    MontFp!("1829645857879599710118729530881719466212267750211549450274626006647955865531"),
+    MontFp!("5194669995904656347813227135329946362866202035008612080679398960455468349856"),
+    MontFp!("152517851289441914339426895806560905925240494857575822709998217410990679443"),
+    MontFp!("801108984839830094666274785331245479261942065449924454877833057328477739939"),
+    MontFp!("7051000157526010943801165200498652232544642915491188576830596595536499002687"),
+    MontFp!("2216432659854733047132347621569505613620980842043977268828076165669557467682"),
+    MontFp!("4418800001734787524987251640050330040572100200707088907985010242893658291716"),
+    MontFp!("472257316771368502865069657152674192844006801340183418049264785940810093802"),
+    MontFp!("591898082059731926865322464669510694084176792554159747638643214876026497355"),
+    MontFp!("4614227581364195720072398759748052409929603551886029816710943423469798880620"),
+    MontFp!("508534288156068795436622693042689714391079994065364903486093384232898120576"),
+    MontFp!("7777922852316062996337157300331910646083702852714396780630056464388736150341"),
+    MontFp!("8341153485941974502078971724658458159765645137234916652153939169154893007862"),
+    MontFp!("8253244735092047732753530257261116539217826234659178651062317836911806165149"),
+    MontFp!("884677124397453365974332400839373604026149779625834449320852275576742732225"),
+    MontFp!("2684107652991839161764237584472196746416389385103613911197239820018868861803"),
+    MontFp!("3882961030227078620454856097073284859664098505176009242080729988555204950592"),
+    MontFp!("6044419018302492240744085068511860953270664370633074724873708288135312403196"),
+    MontFp!("7707390991445273714317147920182095549933675225130098697967669255400856317979"),
+    MontFp!("2392027692032432593956355349966935988122341485573052015805810314199321357112"),
+    MontFp!("6467815055021393437440285680589533803539971219750761917222330908605918256924"),
+    MontFp!("3311013572018025154105127816606052439819382046839164709544455826759555063580"),
+    MontFp!("1675091356440682342827457053739608090747178596352636495639331124481272463377"),
+    MontFp!("5817665832191137403701351752678775176802639635697383890552279107332502141218"),
+    MontFp!("1727448059073003880468010518669528903511390697624445265460309749663011836259"),
+    MontFp!("2785684388229367761168628424572829158367376779525797663885422293667570555316"),
+    MontFp!("6703925662351728291552237935336643942889509277609505206483006425478661049603"),
+    MontFp!("7266047169830996984726911203997753313893371383301030971807333671225925225958"),
+    MontFp!("5081014996586530692661844219623689331456833333397467026161065905573598714285"),
+    MontFp!("4504741217333445854148320251999917599928334590189046243424126867736146166954"),
+    MontFp!("178931496010875552615369881766963384213323205956653188767106908572469657715"),
+    MontFp!("8019328450283110455848283781235557070364611197505432624108449936906394923408"),
+    MontFp!("2362963352605826758690071458128520804513177249581901481674149578090678259761"),
+    MontFp!("582694425648975085227372258164849518853717975679841928722645225176825516088"),
+    MontFp!("8255709983053966483919045503511290051227301519901575242480346243934566794057"),
+    MontFp!("7336683028030699763785320831478469392067611606981435233403193028558393070821"),
+    MontFp!("7321070916726277901235321171814463296233413243426467305689155732571950048209"),
+    MontFp!("7594017890037021425366623750593200398174488805473151513558919864633711506220"),
+    MontFp!("3645142745426229530714966160640087056786140365605704369450590004211944512348"),
 ];
+
+impl MiMCPreset for MIMC_7_90_BLS12_377_PARAMS {
+    type Field = Fr;
+    const NAME: &'static str = "mimc-7-90-bls12-377";
+    const PERMUTATION_TYPE: PermutationType = PermutationType::NonFeistel;
+
+    fn round_keys() -> &'static [Fr] {
+        &MIMC_7_90_BLS12_377_ROUND_KEYS
+    }
+}
+
+impl MiMC<Fr, MIMC_7_90_BLS12_377_PARAMS> {
+    /// Key zero and one output with the bundled round keys, without parsing or validation
+    pub fn mimc_7_90_bls12_377() -> Self {
+        Self::from_preset(&MIMC_7_90_BLS12_377_ROUND_KEYS)
+    }
+}
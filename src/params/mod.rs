@@ -1,8 +1,24 @@
 //! Round constants are generated from
 //! https://github.com/iden3/circomlibjs/blob/main/src/mimcsponge.js
+//!
+//! Every table is [`crate::paramgen::generate_default_round_keys`] for its field,
+//! permutation type and exponent, see [`verify_bundled`]. Round counts are
+//! `ceil(log_e p)`, doubled for feistel, so the 253-bit BLS12-377 scalar field
+//! needs 90 rounds with `x^7` and 218 with `x^5` where the 254-bit BN254 and
+//! 255-bit BLS12-381 fields need 91 and 220. `mimc-7-90-bls12-377` declares `x^5`
+//! for its `x^7` table and is kept that way for compatibility.
 #![allow(non_camel_case_types)]
 
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
+
+#[cfg(feature = "paramgen")]
+use crate::{paramgen::generate_default_round_keys, PermutationType};
+use crate::{DynMiMC, MiMCError};
+
+#[doc(hidden)]
+pub mod const_keys;
+mod preset;
+pub use preset::MiMCPreset;
 
 #[cfg(feature = "mimc-5-218-bls12-377")]
 pub mod mimc_5_218_bls12_377;
@@ -17,11 +33,141 @@ pub mod mimc_7_91_bls12_381;
 #[cfg(feature = "mimc-7-91-bn254")]
 pub mod mimc_7_91_bn254;
 
-pub fn round_keys_contants_to_vec<F: PrimeField>(round_keys: &[&str]) -> Vec<F>
-where
-    F::Err: core::fmt::Debug,
-{
-    round_keys.iter().map(|e| F::from_str(e).unwrap()).collect()
+pub fn round_keys_contants_to_vec<F: PrimeField>(round_keys: &[&str]) -> Result<Vec<F>, MiMCError> {
+    round_keys
+        .iter()
+        .enumerate()
+        .map(|(index, e)| {
+            F::from_str(e).map_err(|_| MiMCError::InvalidRoundKey {
+                index,
+                value: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Names of the presets enabled by cargo features, for [`by_name`]
+#[allow(clippy::vec_init_then_push)]
+pub fn names() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut names = vec![];
+    #[cfg(feature = "mimc-5-218-bls12-377")]
+    names.push(<mimc_5_218_bls12_377::MIMC_5_218_BLS12_377_PARAMS as MiMCPreset>::NAME);
+    #[cfg(feature = "mimc-5-220-bls12-381")]
+    names.push(<mimc_5_220_bls12_381::MIMC_5_220_BLS12_381_PARAMS as MiMCPreset>::NAME);
+    #[cfg(feature = "mimc-5-220-bn254")]
+    names.push(<mimc_5_220_bn254::MIMC_5_220_BN254_PARAMS as MiMCPreset>::NAME);
+    #[cfg(feature = "mimc-7-90-bls12-377")]
+    names.push(<mimc_7_90_bls12_377::MIMC_7_90_BLS12_377_PARAMS as MiMCPreset>::NAME);
+    #[cfg(feature = "mimc-7-91-bls12-381")]
+    names.push(<mimc_7_91_bls12_381::MIMC_7_91_BLS12_381_PARAMS as MiMCPreset>::NAME);
+    #[cfg(feature = "mimc-7-91-bn254")]
+    names.push(<mimc_7_91_bn254::MIMC_7_91_BN254_PARAMS as MiMCPreset>::NAME);
+    names
+}
+
+/// Enabled preset by name, such as `"mimc-7-91-bn254"`, with key zero and one output.
+/// Fails if the preset is not enabled or if its field is not `F`. The typed
+/// [`MiMCPreset::mimc`] rejects a mismatched field at compile time instead.
+pub fn by_name<F: PrimeField>(name: &str) -> Result<DynMiMC<F>, MiMCError> {
+    #[cfg(feature = "mimc-5-218-bls12-377")]
+    if name == <mimc_5_218_bls12_377::MIMC_5_218_BLS12_377_PARAMS as MiMCPreset>::NAME {
+        return preset_to_dyn::<F, mimc_5_218_bls12_377::MIMC_5_218_BLS12_377_PARAMS>();
+    }
+    #[cfg(feature = "mimc-5-220-bls12-381")]
+    if name == <mimc_5_220_bls12_381::MIMC_5_220_BLS12_381_PARAMS as MiMCPreset>::NAME {
+        return preset_to_dyn::<F, mimc_5_220_bls12_381::MIMC_5_220_BLS12_381_PARAMS>();
+    }
+    #[cfg(feature = "mimc-5-220-bn254")]
+    if name == <mimc_5_220_bn254::MIMC_5_220_BN254_PARAMS as MiMCPreset>::NAME {
+        return preset_to_dyn::<F, mimc_5_220_bn254::MIMC_5_220_BN254_PARAMS>();
+    }
+    #[cfg(feature = "mimc-7-90-bls12-377")]
+    if name == <mimc_7_90_bls12_377::MIMC_7_90_BLS12_377_PARAMS as MiMCPreset>::NAME {
+        return preset_to_dyn::<F, mimc_7_90_bls12_377::MIMC_7_90_BLS12_377_PARAMS>();
+    }
+    #[cfg(feature = "mimc-7-91-bls12-381")]
+    if name == <mimc_7_91_bls12_381::MIMC_7_91_BLS12_381_PARAMS as MiMCPreset>::NAME {
+        return preset_to_dyn::<F, mimc_7_91_bls12_381::MIMC_7_91_BLS12_381_PARAMS>();
+    }
+    #[cfg(feature = "mimc-7-91-bn254")]
+    if name == <mimc_7_91_bn254::MIMC_7_91_BN254_PARAMS as MiMCPreset>::NAME {
+        return preset_to_dyn::<F, mimc_7_91_bn254::MIMC_7_91_BN254_PARAMS>();
+    }
+    Err(MiMCError::UnknownPreset(name.to_string()))
+}
+
+/// `P` over `F`, which must have the same modulus as `P::Field`
+#[allow(dead_code)]
+fn preset_to_dyn<F: PrimeField, P: MiMCPreset>() -> Result<DynMiMC<F>, MiMCError> {
+    if F::MODULUS.as_ref() != P::Field::MODULUS.as_ref() {
+        return Err(MiMCError::PresetFieldMismatch(P::NAME.to_string()));
+    }
+    let round_keys = P::round_keys()
+        .iter()
+        .map(|c| F::from_le_bytes_mod_order(&c.into_bigint().to_bytes_le()))
+        .collect();
+    DynMiMC::try_new_unvalidated(P::PERMUTATION_TYPE, P::EXPONENT, 1, F::zero(), round_keys)
+}
+
+/// Regenerate the round keys of every enabled preset with [`generate_default_round_keys`]
+/// and compare them element by element. Fails with the first preset whose exponent
+/// differs from its name, or whose round count or round key differs.
+///
+/// `mimc-7-90-bls12-377` declares `x^5` and fails with
+/// [`MiMCError::PresetExponentMismatch`], it is checked last. Needs the `paramgen`
+/// feature.
+#[cfg(feature = "paramgen")]
+pub fn verify_bundled() -> Result<(), MiMCError> {
+    #[cfg(feature = "mimc-5-218-bls12-377")]
+    verify::<mimc_5_218_bls12_377::MIMC_5_218_BLS12_377_PARAMS>()?;
+    #[cfg(feature = "mimc-5-220-bls12-381")]
+    verify::<mimc_5_220_bls12_381::MIMC_5_220_BLS12_381_PARAMS>()?;
+    #[cfg(feature = "mimc-5-220-bn254")]
+    verify::<mimc_5_220_bn254::MIMC_5_220_BN254_PARAMS>()?;
+    #[cfg(feature = "mimc-7-91-bls12-381")]
+    verify::<mimc_7_91_bls12_381::MIMC_7_91_BLS12_381_PARAMS>()?;
+    #[cfg(feature = "mimc-7-91-bn254")]
+    verify::<mimc_7_91_bn254::MIMC_7_91_BN254_PARAMS>()?;
+    #[cfg(feature = "mimc-7-90-bls12-377")]
+    verify::<mimc_7_90_bls12_377::MIMC_7_90_BLS12_377_PARAMS>()?;
+    Ok(())
+}
+
+#[cfg(feature = "paramgen")]
+#[allow(dead_code)]
+fn verify<P: MiMCPreset>() -> Result<(), MiMCError> {
+    // Names are `mimc-<exponent>-<rounds>-<curve>`
+    if P::NAME.split('-').nth(1) != Some(P::EXPONENT.to_string().as_str()) {
+        return Err(MiMCError::PresetExponentMismatch {
+            preset: P::NAME.to_string(),
+            exponent: P::EXPONENT,
+        });
+    }
+    verify_round_keys(P::NAME, P::PERMUTATION_TYPE, P::EXPONENT, P::round_keys())
+}
+
+#[cfg(feature = "paramgen")]
+fn verify_round_keys<F: PrimeField>(
+    name: &str,
+    permutation_type: PermutationType,
+    exponent: usize,
+    round_keys: &[F],
+) -> Result<(), MiMCError> {
+    let (length, generated) = generate_default_round_keys::<F>(permutation_type, exponent)?;
+    if round_keys.len() != length {
+        return Err(MiMCError::InvalidRoundKeysLength {
+            expected: length,
+            actual: round_keys.len(),
+        });
+    }
+    match round_keys.iter().zip(&generated).position(|(c, g)| c != g) {
+        Some(index) => Err(MiMCError::RoundKeyMismatch {
+            preset: name.to_string(),
+            index,
+        }),
+        None => Ok(()),
+    }
 }
 
 // #[cfg(test)]
@@ -96,3 +242,126 @@ where
 //         Ok(())
 //     }
 // }
+
+#[cfg(test)]
+mod preset_tests {
+    use ark_bls12_377::Fr;
+    use ark_ff::Zero;
+
+    #[cfg(feature = "paramgen")]
+    use crate::{
+        paramgen::{derive_round_keys, generate_default_round_keys},
+        params::{verify_bundled, verify_round_keys},
+    };
+    use crate::{
+        params::{by_name, MiMCPreset},
+        validate, MiMCError, MiMCParameters, PermutationType,
+    };
+
+    crate::mimc_preset!(MIMC_11_73_TEST, "mimc-11-73-test", Fr, 11, 73, b"mimc", non_feistel);
+    crate::mimc_preset!(MIMC_11_146_TEST, "mimc-11-146-test", Fr, 11, 146, b"mimcsponge", feistel);
+
+    #[test]
+    fn preset_macro() {
+        assert_eq!(MIMC_11_73_TEST::PERMUTATION_TYPE, PermutationType::NonFeistel);
+        assert_eq!(validate::<Fr, MIMC_11_73_TEST>(), Ok(()));
+        #[cfg(feature = "paramgen")]
+        assert_eq!(
+            MIMC_11_73_TEST::round_keys(),
+            derive_round_keys::<Fr>(PermutationType::NonFeistel, 73, b"mimc")
+        );
+
+        let mimc = MIMC_11_146_TEST::mimc();
+        assert_eq!(mimc.round_keys.len(), MIMC_11_146_TEST::ROUNDS);
+        assert_eq!(mimc.round_keys[0], Fr::zero());
+        assert_eq!(mimc.round_keys[145], Fr::zero());
+        assert_ne!(mimc.round_keys[144], Fr::zero());
+        assert_eq!(MIMC_11_146_TEST::NAME, "mimc-11-146-test");
+    }
+
+    #[test]
+    fn registry() {
+        assert_eq!(
+            by_name::<Fr>("mimc-11-73-test"),
+            Err(MiMCError::UnknownPreset("mimc-11-73-test".to_string()))
+        );
+
+        #[cfg(feature = "mimc-7-91-bn254")]
+        {
+            use crate::params::mimc_7_91_bn254::MIMC_7_91_BN254_PARAMS;
+
+            assert!(super::names().contains(&"mimc-7-91-bn254"));
+            assert_eq!(
+                by_name::<ark_bn254::Fr>("mimc-7-91-bn254"),
+                Ok(MIMC_7_91_BN254_PARAMS::mimc().to_dyn(PermutationType::NonFeistel))
+            );
+            assert_eq!(
+                by_name::<Fr>("mimc-7-91-bn254"),
+                Err(MiMCError::PresetFieldMismatch("mimc-7-91-bn254".to_string()))
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "mimc-7-91-bn254")]
+    fn const_round_keys_match_decimal() {
+        use ark_bn254::Fr;
+        use ark_ff::Zero;
+
+        use crate::{
+            params::{
+                mimc_7_91_bn254::{MIMC_7_91_BN254_PARAMS, MIMC_7_91_BN254_ROUND_KEYS},
+                round_keys_contants_to_vec,
+            },
+            MiMC,
+        };
+
+        let parsed = round_keys_contants_to_vec::<Fr>(&[
+            "0",
+            "20888961410941983456478427210666206549300505294776164667214940546594746570981",
+        ])
+        .unwrap();
+        assert_eq!(MIMC_7_91_BN254_ROUND_KEYS[..2], parsed[..]);
+
+        let mimc = MiMC::<Fr, MIMC_7_91_BN254_PARAMS>::mimc_7_91_bn254();
+        assert_eq!(
+            MiMC::try_new(1, Fr::zero(), MIMC_7_91_BN254_ROUND_KEYS.to_vec()),
+            Ok(mimc)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "paramgen")]
+    fn bundled_presets_match_paramgen() {
+        #[cfg(not(feature = "mimc-7-90-bls12-377"))]
+        assert_eq!(verify_bundled(), Ok(()));
+        #[cfg(feature = "mimc-7-90-bls12-377")]
+        assert_eq!(
+            verify_bundled(),
+            Err(MiMCError::PresetExponentMismatch {
+                preset: "mimc-7-90-bls12-377".to_string(),
+                exponent: 5
+            })
+        );
+
+        let (_, mut round_keys) =
+            generate_default_round_keys::<Fr>(PermutationType::Feistel, 5).unwrap();
+        assert_eq!(round_keys.len(), 218);
+        assert_eq!(verify_round_keys("test", PermutationType::Feistel, 5, &round_keys), Ok(()));
+        round_keys[100] += Fr::from(1);
+        assert_eq!(
+            verify_round_keys("test", PermutationType::Feistel, 5, &round_keys),
+            Err(MiMCError::RoundKeyMismatch {
+                preset: "test".to_string(),
+                index: 100
+            })
+        );
+        assert_eq!(
+            verify_round_keys("test", PermutationType::NonFeistel, 7, &round_keys[..91]),
+            Err(MiMCError::InvalidRoundKeysLength {
+                expected: 90,
+                actual: 91
+            })
+        );
+    }
+}
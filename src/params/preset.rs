@@ -0,0 +1,86 @@
+use ark_ff::PrimeField;
+
+use crate::{MiMC, MiMCParameters, PermutationType};
+
+/// Parameters bound to the field and permutation type their round keys were generated for,
+/// so a preset can only be instantiated over its own field
+pub trait MiMCPreset: MiMCParameters {
+    type Field: PrimeField;
+    /// Registry name, see [`super::by_name`]
+    const NAME: &'static str;
+    const PERMUTATION_TYPE: PermutationType;
+
+    fn round_keys() -> &'static [Self::Field];
+
+    /// Key zero and one output, without validation
+    fn mimc() -> MiMC<Self::Field, Self> {
+        MiMC::from_preset(Self::round_keys())
+    }
+}
+
+/// Define a preset in one line: a params type implementing [`MiMCParameters`] and
+/// [`MiMCPreset`] under the registry name `$id`, with its round keys derived from `seed`
+/// as [`crate::paramgen::derive_round_keys`] does.
+///
+/// The round keys are computed at compile time into the constant `ROUND_KEYS`, and
+/// it is a compile error if `x^exponent` does not permute the field or if there are
+/// fewer than `ceil(log_e p)` rounds, doubled for feistel. The field must have a
+/// Montgomery backend, as every arkworks curve field does.
+///
+/// ```ignore
+/// mimc_preset!(pub MIMC_7_91_IN_HOUSE, "mimc-7-91-in-house", ark_bn254::Fr, 7, 91, b"in-house", non_feistel);
+///
+/// let mimc = <MIMC_7_91_IN_HOUSE as MiMCPreset>::mimc();
+/// ```
+#[macro_export]
+macro_rules! mimc_preset {
+    (@kind feistel) => {
+        $crate::PermutationType::Feistel
+    };
+    (@kind non_feistel) => {
+        $crate::PermutationType::NonFeistel
+    };
+    (@feistel feistel) => {
+        true
+    };
+    (@feistel non_feistel) => {
+        false
+    };
+    ($vis:vis $name:ident, $id:literal, $field:ty, $exponent:expr, $rounds:expr, $seed:expr, $kind:ident) => {
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Default)]
+        $vis struct $name;
+
+        impl $crate::MiMCParameters for $name {
+            const ROUNDS: usize = $rounds;
+            const EXPONENT: usize = $exponent;
+            const PRESET_ID: Option<&'static str> =
+                Some(<Self as $crate::params::MiMCPreset>::NAME);
+        }
+
+        impl $name {
+            pub const SEED: &'static [u8] = $seed;
+            /// Round keys derived from [`Self::SEED`] at compile time
+            pub const ROUND_KEYS: [$field; $rounds] = $crate::params::const_keys::round_keys(
+                $exponent,
+                $crate::mimc_preset!(@feistel $kind),
+                Self::SEED,
+            );
+        }
+
+        // Evaluate the table and its checks even if the preset is never used
+        const _: () = {
+            let _ = $name::ROUND_KEYS;
+        };
+
+        impl $crate::params::MiMCPreset for $name {
+            type Field = $field;
+            const NAME: &'static str = $id;
+            const PERMUTATION_TYPE: $crate::PermutationType = $crate::mimc_preset!(@kind $kind);
+
+            fn round_keys() -> &'static [$field] {
+                &Self::ROUND_KEYS
+            }
+        }
+    };
+}
@@ -0,0 +1,208 @@
+//! Cost estimates for the known attacks on MiMC, after the MiMC paper
+//! (Albrecht et al., ASIACRYPT 2016) and the later algebraic analyses of it.
+//!
+//! All costs are `log2` of the number of field operations or queries. The
+//! interpolation style attacks are bounded by `d = e^r`, the degree of the
+//! permutation in its input or key, and by `p`, since no polynomial over `F`
+//! needs a degree above `p`. Feistel rounds only count half, as each branch
+//! goes through `x^e` every other round, matching the doubled round count of
+//! [`crate::paramgen`].
+
+use ark_ff::PrimeField;
+
+use crate::{
+    params::MiMCPreset,
+    validation::{is_permutation, min_rounds},
+    MiMCError, PermutationType,
+};
+
+/// Linear algebra constant used for Gröbner basis costs, the conservative choice
+const OMEGA: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attack {
+    /// Recover the key or the permutation from `d + 1` pairs by interpolating
+    /// the output polynomial, `O(d log d)`
+    Interpolation,
+    /// Key recovery from the gcd of two polynomials of degree `d` in the key,
+    /// `O(d log^2 d)`
+    Gcd,
+    /// Solve the system of one degree `e` equation per round, with degree of
+    /// regularity `1 + r (e - 1)`
+    GroebnerBasis,
+    /// Distinguish with a vanishing `(d + 1)`-th order difference, `d + 1` queries
+    HigherOrderDifferential,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttackCost {
+    pub attack: Attack,
+    /// `log2` of the cost
+    pub bits: f64,
+}
+
+/// Costs of every [`Attack`] on one parameter set
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityEstimate {
+    pub permutation_type: PermutationType,
+    pub exponent: usize,
+    pub rounds: usize,
+    /// `log2 p`
+    pub field_bits: f64,
+    /// Smallest round count with `e^r >= p`, doubled for feistel
+    pub min_rounds: usize,
+    pub costs: Vec<AttackCost>,
+}
+
+impl SecurityEstimate {
+    /// The cheapest attack
+    pub fn weakest(&self) -> AttackCost {
+        *self
+            .costs
+            .iter()
+            .min_by(|a, b| a.bits.total_cmp(&b.bits))
+            .expect("every attack is estimated")
+    }
+
+    /// Cost of the cheapest attack in bits
+    pub fn security_bits(&self) -> f64 {
+        self.weakest().bits
+    }
+
+    /// Bits of security above `target_bits`, negative if an attack is cheaper
+    pub fn margin_bits(&self, target_bits: f64) -> f64 {
+        self.security_bits() - target_bits
+    }
+}
+
+impl std::fmt::Display for SecurityEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:?} x^{} with {} rounds over a {:.1}-bit field, at least {} rounds needed",
+            self.permutation_type, self.exponent, self.rounds, self.field_bits, self.min_rounds
+        )?;
+        for cost in &self.costs {
+            writeln!(f, "  {:?}: {:.1} bits", cost.attack, cost.bits)?;
+        }
+        write!(f, "  security: {:.1} bits", self.security_bits())
+    }
+}
+
+/// Estimate the attacks on `rounds` rounds of `x^exponent` over `F`. Reduced round
+/// counts are accepted, only an exponent that is not a permutation of `F` fails.
+pub fn estimate<F: PrimeField>(
+    permutation_type: PermutationType,
+    exponent: usize,
+    rounds: usize,
+) -> Result<SecurityEstimate, MiMCError> {
+    let min_rounds = min_rounds::<F>(exponent)?;
+    if !is_permutation::<F>(exponent) {
+        return Err(MiMCError::ExponentNotPermutation(exponent));
+    }
+    let (effective_rounds, min_rounds) = match permutation_type {
+        PermutationType::Feistel => (rounds / 2, min_rounds * 2),
+        PermutationType::NonFeistel => (rounds, min_rounds),
+    };
+
+    let field_bits = F::MODULUS
+        .as_ref()
+        .iter()
+        .rev()
+        .fold(0f64, |acc, limb| acc * 2f64.powi(64) + *limb as f64)
+        .log2();
+    let degree_bits = (effective_rounds as f64 * (exponent as f64).log2()).min(field_bits);
+    let log_degree_bits = degree_bits.max(1.0).log2();
+    let regularity = 1 + effective_rounds * (exponent - 1);
+
+    Ok(SecurityEstimate {
+        permutation_type,
+        exponent,
+        rounds,
+        field_bits,
+        min_rounds,
+        costs: vec![
+            AttackCost {
+                attack: Attack::Interpolation,
+                bits: degree_bits + log_degree_bits,
+            },
+            AttackCost {
+                attack: Attack::Gcd,
+                bits: degree_bits + 2.0 * log_degree_bits,
+            },
+            AttackCost {
+                attack: Attack::GroebnerBasis,
+                bits: OMEGA * log2_binomial(effective_rounds + regularity, regularity),
+            },
+            AttackCost {
+                attack: Attack::HigherOrderDifferential,
+                bits: degree_bits,
+            },
+        ],
+    })
+}
+
+/// [`estimate`] for a preset
+pub fn estimate_preset<P: MiMCPreset>() -> Result<SecurityEstimate, MiMCError> {
+    estimate::<P::Field>(P::PERMUTATION_TYPE, P::EXPONENT, P::ROUNDS)
+}
+
+/// `log2` of `n` choose `k`
+fn log2_binomial(n: usize, k: usize) -> f64 {
+    let k = k.min(n - k);
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).log2()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+
+    use super::{estimate, log2_binomial, Attack};
+    use crate::{MiMCError, PermutationType};
+
+    #[test]
+    fn full_rounds_reach_field_size() {
+        let non_feistel = estimate::<Fr>(PermutationType::NonFeistel, 7, 91).unwrap();
+        assert_eq!(non_feistel.min_rounds, 91);
+        assert_eq!(non_feistel.weakest().attack, Attack::HigherOrderDifferential);
+        assert!((non_feistel.security_bits() - non_feistel.field_bits).abs() < 1e-9);
+        assert!(non_feistel.margin_bits(128.0) > 125.0);
+
+        let feistel = estimate::<Fr>(PermutationType::Feistel, 5, 220).unwrap();
+        assert_eq!(feistel.min_rounds, 220);
+        assert_eq!(feistel.security_bits(), non_feistel.security_bits());
+    }
+
+    #[test]
+    fn reduced_rounds() {
+        // 40 * log2(7) ~ 112.3
+        let non_feistel = estimate::<Fr>(PermutationType::NonFeistel, 7, 40).unwrap();
+        assert!((non_feistel.security_bits() - 112.29).abs() < 0.01);
+        assert!(non_feistel.margin_bits(128.0) < 0.0);
+        // Feistel rounds count half
+        let feistel = estimate::<Fr>(PermutationType::Feistel, 7, 80).unwrap();
+        assert_eq!(feistel.security_bits(), non_feistel.security_bits());
+
+        assert!((log2_binomial(10, 3) - 120f64.log2()).abs() < 1e-9);
+        assert_eq!(
+            estimate::<Fr>(PermutationType::NonFeistel, 3, 200),
+            Err(MiMCError::ExponentNotPermutation(3))
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "mimc-7-91-bn254", feature = "mimc-7-90-bls12-377"))]
+    fn bundled_presets() {
+        use super::estimate_preset;
+        use crate::params::{
+            mimc_7_90_bls12_377::MIMC_7_90_BLS12_377_PARAMS,
+            mimc_7_91_bn254::MIMC_7_91_BN254_PARAMS,
+        };
+
+        assert!(estimate_preset::<MIMC_7_91_BN254_PARAMS>().unwrap().margin_bits(128.0) > 0.0);
+        assert_eq!(
+            estimate_preset::<MIMC_7_90_BLS12_377_PARAMS>(),
+            Err(MiMCError::ExponentNotPermutation(5))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+use crate::{MiMC, MiMCParameters};
+
+/// `P::ROUNDS`, `P::EXPONENT` and `P::PRESET_ID`, written before the parameters and
+/// checked against `P` on read so parameters are never loaded with the wrong settings
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    rounds: u64,
+    exponent: u64,
+    preset_id: Option<String>,
+}
+
+impl Header {
+    fn of<P: MiMCParameters>() -> Self {
+        Self {
+            rounds: P::ROUNDS as u64,
+            exponent: P::EXPONENT as u64,
+            preset_id: P::PRESET_ID.map(str::to_string),
+        }
+    }
+
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.rounds.serialize_with_mode(&mut writer, compress)?;
+        self.exponent.serialize_with_mode(&mut writer, compress)?;
+        self.preset_id.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.rounds.serialized_size(compress)
+            + self.exponent.serialized_size(compress)
+            + self.preset_id.serialized_size(compress)
+    }
+
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            rounds: u64::deserialize_with_mode(&mut reader, compress, validate)?,
+            exponent: u64::deserialize_with_mode(&mut reader, compress, validate)?,
+            preset_id: Option::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> CanonicalSerialize for MiMC<F, P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        Header::of::<P>().serialize_with_mode(&mut writer, compress)?;
+        (self.num_outputs as u64).serialize_with_mode(&mut writer, compress)?;
+        self.k.serialize_with_mode(&mut writer, compress)?;
+        self.round_keys.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        Header::of::<P>().serialized_size(compress)
+            + (self.num_outputs as u64).serialized_size(compress)
+            + self.k.serialized_size(compress)
+            + self.round_keys.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> Valid for MiMC<F, P> {
+    /// Field elements are checked as they are read, and the round keys length by
+    /// [`MiMC::try_new_unvalidated`]. [`crate::validate`] is not enforced since both
+    /// bundled BLS12-377 presets fail it, `x^5` and `x^7` are not permutations there.
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, P: MiMCParameters> CanonicalDeserialize for MiMC<F, P> {
+    /// Fails with [`SerializationError::InvalidData`] if the header differs from `P`
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        if Header::deserialize_with_mode(&mut reader, compress, validate)? != Header::of::<P>() {
+            return Err(SerializationError::InvalidData);
+        }
+        let num_outputs = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let k = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        let round_keys = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+        Self::try_new_unvalidated(num_outputs as usize, k, round_keys)
+            .map_err(|_| SerializationError::InvalidData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use ark_bls12_377::Fr;
+    use ark_r1cs_std::{prelude::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+    use ark_std::test_rng;
+
+    use crate::{constraints::MiMCVar, MiMC, MiMCParameters};
+
+    macro_rules! mimc_mock {
+        ($name:ident, $rounds:literal, $exp:literal) => {
+            #[derive(Clone, Default)]
+            struct $name;
+
+            impl MiMCParameters for $name {
+                const ROUNDS: usize = $rounds;
+                const EXPONENT: usize = $exp;
+            }
+        };
+    }
+
+    mimc_mock!(MiMCMock, 73, 11);
+    mimc_mock!(MiMCMockExponent, 73, 13);
+    mimc_mock!(MiMCMockRounds, 74, 11);
+
+    crate::mimc_preset!(MIMC_11_73_TEST, "mimc-11-73-test", Fr, 11, 73, b"mimc", non_feistel);
+
+    #[test]
+    fn round_trip() -> Result<(), Box<dyn Error>> {
+        let mut mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(&mut test_rng());
+        mimc.num_outputs = 2;
+
+        let mut compressed = vec![];
+        mimc.serialize_compressed(&mut compressed)?;
+        assert_eq!(compressed.len(), mimc.compressed_size());
+        assert_eq!(MiMC::<Fr, MiMCMock>::deserialize_compressed(&*compressed)?, mimc);
+
+        let mut uncompressed = vec![];
+        mimc.serialize_uncompressed(&mut uncompressed)?;
+        let read = MiMC::<Fr, MiMCMock>::deserialize_uncompressed(&*uncompressed)?;
+        assert_eq!(read, mimc);
+        assert_eq!(read.permute_feistel(vec![Fr::from(1)]), mimc.permute_feistel(vec![Fr::from(1)]));
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let mimc_var = MiMCVar::<_, MiMCMock>::new_witness(cs, || Ok(&read))?;
+        assert_eq!(mimc_var.value()?, mimc);
+        Ok(())
+    }
+
+    #[test]
+    fn header_mismatch() -> Result<(), Box<dyn Error>> {
+        let mimc = MiMC::<Fr, MiMCMock>::rand_unvalidated(&mut test_rng());
+        let mut bytes = vec![];
+        mimc.serialize_compressed(&mut bytes)?;
+
+        assert!(matches!(
+            MiMC::<Fr, MiMCMockExponent>::deserialize_compressed(&*bytes),
+            Err(SerializationError::InvalidData)
+        ));
+        assert!(matches!(
+            MiMC::<Fr, MiMCMockRounds>::deserialize_compressed(&*bytes),
+            Err(SerializationError::InvalidData)
+        ));
+        // Same rounds and exponent, different preset
+        assert!(matches!(
+            MiMC::<Fr, MIMC_11_73_TEST>::deserialize_compressed(&*bytes),
+            Err(SerializationError::InvalidData)
+        ));
+        Ok(())
+    }
+}
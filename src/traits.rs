@@ -1,11 +1,9 @@
-use rand::rngs::OsRng;
 use std::{borrow::Borrow, marker::PhantomData};
 
-use ark_crypto_primitives::{crh::{sha256::digest::typenum::Len, CRHScheme, TwoToOneCRHScheme}, Error};
-use ark_ff::{PrimeField};
-use ark_std::iterable::Iterable;
+use ark_crypto_primitives::{crh::{CRHScheme, TwoToOneCRHScheme}, Error};
+use ark_ff::PrimeField;
 
-use crate::{utils::to_field_elements, MiMC, MiMCParameters};
+use crate::{utils::to_field_elements, MiMC, MiMCError, MiMCParameters, PermutationType};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct MiMCFeistelCRH<F: PrimeField, P: MiMCParameters>(PhantomData<F>, PhantomData<P>);
@@ -91,7 +89,6 @@ impl<F: PrimeField, P: MiMCParameters> std::fmt::Debug for MiMC<F, P> {
 // }
 
 // const INPUT_SIZE_BITS: usize = <F::Params as FpParameters>::CAPACITY as usize;
-const INPUT_SIZE_BITS: usize = 255;     // capacity(?) for bn254::Fr
 impl<F: PrimeField, P: MiMCParameters> CRHScheme for MiMCNonFeistelCRH<F, P> {
     type Input = [u8];
     type Output = F;
@@ -101,12 +98,16 @@ impl<F: PrimeField, P: MiMCParameters> CRHScheme for MiMCNonFeistelCRH<F, P> {
     fn setup<R: ark_std::rand::Rng>(
         r: &mut R,
     ) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
-        Ok(Self::Parameters {
-            num_outputs: 1,
-            params: PhantomData,
-            k: F::rand(r),
-            round_keys: (0..P::ROUNDS).map(|_| F::rand(r)).collect::<Vec<_>>(),
-        })
+        Ok(Self::Parameters::try_new(
+            1,
+            F::rand(r),
+            (0..P::ROUNDS)
+                .map(|i| match i == 0 {
+                    true => F::zero(),
+                    false => F::rand(r),
+                })
+                .collect::<Vec<_>>(),
+        )?)
     }
 
     fn evaluate<T: Borrow<Self::Input>>(
@@ -120,8 +121,6 @@ impl<F: PrimeField, P: MiMCParameters> CRHScheme for MiMCNonFeistelCRH<F, P> {
 }
 
 // const INPUT_SIZE_BITS: usize = 32;
-const LEFT_INPUT_SIZE_BITS: usize = INPUT_SIZE_BITS;
-const RIGHT_INPUT_SIZE_BITS: usize = INPUT_SIZE_BITS;
 
 impl<F: PrimeField, P: MiMCParameters> TwoToOneCRHScheme for MiMCNonFeistelCRH<F, P> {
     type Input = [u8];
@@ -142,7 +141,13 @@ impl<F: PrimeField, P: MiMCParameters> TwoToOneCRHScheme for MiMCNonFeistelCRH<F
     ) -> Result<Self::Output, ark_crypto_primitives::Error> {
         let left_input = left_input.borrow();
         let right_input = right_input.borrow();
-        assert_eq!(left_input.len(), right_input.len());
+        if left_input.len() != right_input.len() {
+            return Err(MiMCError::InputLengthMismatch {
+                left: left_input.len(),
+                right: right_input.len(),
+            }
+            .into());
+        }
         let chained: Vec<_> = left_input
             .iter()
             .chain(right_input.iter())
@@ -156,7 +161,10 @@ impl<F: PrimeField, P: MiMCParameters> TwoToOneCRHScheme for MiMCNonFeistelCRH<F
         left_input: T,
         right_input: T,
     ) -> Result<Self::Output, Error> {
-        let rng = &mut OsRng;
-        Ok(F::rand(rng))
+        Ok(parameters.compress(
+            PermutationType::NonFeistel,
+            *left_input.borrow(),
+            *right_input.borrow(),
+        ))
     }
 }
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use ark_ff::{BigInteger, PrimeField};
+
+use crate::{MiMCError, MiMCParameters};
+
+/// `limbs * factor` on little-endian 64-bit limbs, growing as needed
+fn mul_small(limbs: &mut Vec<u64>, factor: u64) {
+    let mut carry = 0u128;
+    for limb in limbs.iter_mut() {
+        let product = *limb as u128 * factor as u128 + carry;
+        *limb = product as u64;
+        carry = product >> 64;
+    }
+    if carry > 0 {
+        limbs.push(carry as u64);
+    }
+}
+
+/// `a >= b` on little-endian 64-bit limbs
+fn limbs_ge(a: &[u64], b: &[u64]) -> bool {
+    let len = a.len().max(b.len());
+    for i in (0..len).rev() {
+        let (x, y) = (a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0));
+        if x != y {
+            return x > y;
+        }
+    }
+    true
+}
+
+/// Smallest `r` with `exponent^r >= p`, i.e. `ceil(log_e p)` computed exactly
+pub(crate) fn min_rounds<F: PrimeField>(exponent: usize) -> Result<usize, MiMCError> {
+    rounds_to_reach(exponent, F::MODULUS.as_ref())
+}
+
+/// Smallest `r` with `exponent^r >= bound`, `bound` in little-endian 64-bit limbs
+pub(crate) fn rounds_to_reach(exponent: usize, bound: &[u64]) -> Result<usize, MiMCError> {
+    if exponent < 2 {
+        return Err(MiMCError::InvalidExponent(exponent));
+    }
+    let mut power = vec![1u64];
+    let mut rounds = 0;
+    while !limbs_ge(&power, bound) {
+        mul_small(&mut power, exponent as u64);
+        rounds += 1;
+    }
+    Ok(rounds)
+}
+
+/// `(p - 1) mod divisor`
+pub(crate) fn modulus_minus_one_rem<F: PrimeField>(divisor: u64) -> u64 {
+    let mut modulus = F::MODULUS;
+    modulus.sub_with_borrow(&F::BigInt::from(1u64));
+    modulus
+        .as_ref()
+        .iter()
+        .rev()
+        .fold(0u128, |rem, limb| ((rem << 64) | *limb as u128) % divisor as u128) as u64
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+/// `gcd(e, p - 1) = 1`, so `x^e` is a permutation of `F`
+pub(crate) fn is_permutation<F: PrimeField>(exponent: usize) -> bool {
+    gcd(exponent as u64, modulus_minus_one_rem::<F>(exponent as u64)) == 1
+}
+
+/// Check that `x^P::EXPONENT` is a permutation of `F`, i.e. `gcd(e, p - 1) = 1`, and that
+/// `P::ROUNDS >= ceil(log_e p)`, the bound [`crate::paramgen`] uses for non-feistel rounds.
+/// The same `MiMC` serves both permutation types, so feistel's doubled bound is not enforced.
+pub fn validate<F: PrimeField, P: MiMCParameters>() -> Result<(), MiMCError> {
+    validate_parameters::<F>(P::EXPONENT, P::ROUNDS)
+}
+
+/// [`validate`] for an exponent and round count known only at runtime
+pub fn validate_parameters<F: PrimeField>(exponent: usize, rounds: usize) -> Result<(), MiMCError> {
+    let minimum = min_rounds::<F>(exponent)?;
+    if !is_permutation::<F>(exponent) {
+        return Err(MiMCError::ExponentNotPermutation(exponent));
+    }
+    if rounds < minimum {
+        return Err(MiMCError::TooFewRounds { rounds, minimum });
+    }
+    Ok(())
+}
+
+/// Check that the first round key is zero, as the first round only adds the key,
+/// and that no nonzero round key repeats. Zero keys may repeat since feistel
+/// round keys also zero the last one.
+pub fn validate_round_keys<F: PrimeField>(round_keys: &[F]) -> Result<(), MiMCError> {
+    if round_keys.first().is_some_and(|c| !c.is_zero()) {
+        return Err(MiMCError::NonZeroFirstRoundKey);
+    }
+    let mut seen = HashMap::new();
+    for (index, c) in round_keys.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+        if let Some(first) = seen.insert(*c, index) {
+            return Err(MiMCError::DuplicateRoundKey {
+                first,
+                second: index,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_377::Fr;
+    use ark_ff::{One, Zero};
+
+    use crate::{MiMCError, MiMCParameters};
+
+    use super::{min_rounds, validate, validate_round_keys};
+
+    macro_rules! params {
+        ($name:ident, $exp:literal, $rounds:literal) => {
+            #[derive(Clone, Default)]
+            struct $name;
+
+            impl MiMCParameters for $name {
+                const ROUNDS: usize = $rounds;
+                const EXPONENT: usize = $exp;
+            }
+        };
+    }
+
+    params!(Secure, 11, 73);
+    params!(TooFew, 11, 72);
+    params!(NotPermutation, 3, 220);
+
+    #[test]
+    fn exponent_and_rounds() {
+        assert_eq!(min_rounds::<Fr>(11), Ok(73));
+        assert_eq!(min_rounds::<Fr>(1), Err(MiMCError::InvalidExponent(1)));
+        assert_eq!(validate::<Fr, Secure>(), Ok(()));
+        assert_eq!(
+            validate::<Fr, TooFew>(),
+            Err(MiMCError::TooFewRounds {
+                rounds: 72,
+                minimum: 73
+            })
+        );
+        // 3 | p - 1 for the BLS12-377 scalar field, so x^3 is not a permutation
+        assert_eq!(
+            validate::<Fr, NotPermutation>(),
+            Err(MiMCError::ExponentNotPermutation(3))
+        );
+    }
+
+    #[test]
+    fn round_keys() {
+        let two = Fr::one() + Fr::one();
+        assert_eq!(validate_round_keys(&[Fr::zero(), Fr::one(), two, Fr::zero()]), Ok(()));
+        assert_eq!(
+            validate_round_keys(&[Fr::one(), two]),
+            Err(MiMCError::NonZeroFirstRoundKey)
+        );
+        assert_eq!(
+            validate_round_keys(&[Fr::zero(), two, Fr::one(), two]),
+            Err(MiMCError::DuplicateRoundKey {
+                first: 1,
+                second: 3
+            })
+        );
+    }
+}
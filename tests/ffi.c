@@ -0,0 +1,68 @@
+/* Exercises the C ABI of src/ffi.rs, run by tests/ffi.rs */
+#include <stdio.h>
+#include <string.h>
+
+#include "arkworks_mimc.h"
+
+#define CHECK(condition)                                                  \
+    do {                                                                  \
+        if (!(condition)) {                                               \
+            fprintf(stderr, "%s:%d: %s failed\n", __FILE__, __LINE__,     \
+                    #condition);                                          \
+            return 1;                                                     \
+        }                                                                 \
+    } while (0)
+
+/* Little-endian field element holding a small integer */
+static void field_element(uint8_t out[MIMC_FIELD_BYTES], uint8_t value) {
+    memset(out, 0, MIMC_FIELD_BYTES);
+    out[0] = value;
+}
+
+int main(void) {
+    /* circomlibjs mimcsponge multiHash([1, 2]) */
+    static const uint8_t expected[MIMC_FIELD_BYTES] = {
+        0x6f, 0x2a, 0x6d, 0x3d, 0x1e, 0xff, 0x39, 0x40, 0x94, 0x3a, 0x8e,
+        0xbb, 0x75, 0x70, 0xc4, 0x27, 0x94, 0xe8, 0x4a, 0xcd, 0x73, 0xaf,
+        0xce, 0xf1, 0x03, 0x16, 0x25, 0xa1, 0x35, 0xa0, 0xce, 0x2b,
+    };
+    uint8_t left[MIMC_FIELD_BYTES], right[MIMC_FIELD_BYTES], out[MIMC_FIELD_BYTES];
+    uint8_t inputs[2 * MIMC_FIELD_BYTES], outputs[2 * MIMC_FIELD_BYTES];
+    MiMCHandle *handle = NULL;
+
+    CHECK(mimc_new("mimc-5-220-bn254", &handle) == MiMCStatus_Ok);
+    CHECK(handle != NULL);
+
+    field_element(left, 1);
+    field_element(right, 2);
+    CHECK(mimc_compress(handle, left, right, out) == MiMCStatus_Ok);
+    CHECK(memcmp(out, expected, MIMC_FIELD_BYTES) == 0);
+
+    /* The first output of permuting [1, 2] is the compression */
+    memcpy(inputs, left, MIMC_FIELD_BYTES);
+    memcpy(inputs + MIMC_FIELD_BYTES, right, MIMC_FIELD_BYTES);
+    CHECK(mimc_hash(handle, inputs, 2, outputs, 2) == MiMCStatus_Ok);
+    CHECK(memcmp(outputs, expected, MIMC_FIELD_BYTES) == 0);
+
+    /* Errors are reported instead of aborting */
+    memset(left, 0xff, MIMC_FIELD_BYTES);
+    CHECK(mimc_compress(handle, left, right, out) == MiMCStatus_InvalidFieldElement);
+    CHECK(mimc_hash(handle, inputs, 2, outputs, MIMC_MAX_OUTPUTS + 1) == MiMCStatus_InvalidLength);
+    CHECK(mimc_hash(handle, inputs, SIZE_MAX, outputs, 1) == MiMCStatus_InvalidLength);
+    CHECK(mimc_hash(handle, inputs, 0, outputs, 1) == MiMCStatus_InvalidLength);
+    CHECK(mimc_hash(NULL, inputs, 2, outputs, 1) == MiMCStatus_NullPointer);
+    mimc_free(handle);
+
+    handle = NULL;
+    CHECK(mimc_new("mimc-5-220-bn256", &handle) == MiMCStatus_UnknownPreset);
+    CHECK(handle == NULL);
+
+    /* Another field */
+    CHECK(mimc_new("mimc-7-91-bls12-381", &handle) == MiMCStatus_Ok);
+    CHECK(mimc_compress(handle, inputs, inputs + MIMC_FIELD_BYTES, out) == MiMCStatus_Ok);
+    mimc_free(handle);
+    mimc_free(NULL);
+
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,61 @@
+//! Checks that `include/arkworks_mimc.h` matches `src/ffi.rs`, then builds the
+//! crate as a static library, compiles `tests/ffi.c` against the header and the
+//! library and runs it.
+#![cfg(target_os = "linux")]
+
+use std::{env, fs, path::Path, process::Command};
+
+const HEADER: &str = "include/arkworks_mimc.h";
+
+#[test]
+fn header_is_current() {
+    let config = cbindgen::Config::from_file("cbindgen.toml").unwrap();
+    let mut header = vec![];
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/ffi.rs")
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    if env::var_os("BLESS").is_some() {
+        fs::write(HEADER, &header).unwrap();
+    }
+    assert!(
+        fs::read(HEADER).unwrap() == header,
+        "{HEADER} is out of date, regenerate it with `BLESS=1 cargo test --features ffi --test ffi`"
+    );
+}
+
+#[test]
+fn c_program() {
+    // A target directory of its own keeps the library at a fixed path
+    let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi-staticlib");
+    let status = Command::new(env::var("CARGO").unwrap_or("cargo".to_string()))
+        .args(["rustc", "--lib", "--crate-type", "staticlib", "--features", "ffi"])
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target)
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the static library failed");
+    let library = target.join("debug/libarkworks_mimc.a");
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+
+    let status = Command::new(env::var("CC").unwrap_or("cc".to_string()))
+        .args(["-std=c99", "-Wall", "-Werror", "-Iinclude", "tests/ffi.c"])
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling tests/ffi.c failed");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,265 @@
+//! Drives the `mimc-server` binary over TCP and a Unix socket with a minimal
+//! HTTP/1.1 client, checking the answers against the library.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    process::{Child, Command, Stdio},
+};
+
+use ark_bn254::Fr;
+use arkworks_mimc::{encoding::MerkleProofDocument, merkle::MerkleTree, params, DynMiMC};
+use serde_json::{json, Value};
+
+/// Server process, killed on drop
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    fn start(args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mimc-server"))
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let address = line
+            .trim()
+            .strip_prefix("listening on ")
+            .unwrap_or_else(|| panic!("unexpected banner {line:?}"))
+            .to_string();
+        Self { child, address }
+    }
+
+    fn request(&self, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+        request(
+            TcpStream::connect(&self.address).unwrap(),
+            method,
+            path,
+            body,
+        )
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn request(
+    mut stream: impl Read + Write,
+    method: &str,
+    path: &str,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let body = body.map(|body| body.to_string()).unwrap_or_default();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn hashes_over_http() {
+    let server = Server::start(&["--preset", "mimc-5-220-bn254", "--listen", "127.0.0.1:0"]);
+
+    let (status, info) = server.request("GET", "/info", None);
+    assert_eq!(status, 200);
+    assert_eq!(
+        info,
+        json!({"preset": "mimc-5-220-bn254", "permutation_type": "feistel", "exponent": 5, "rounds": 220})
+    );
+
+    // circomlibjs mimcsponge multiHash([1, 2])
+    let (status, hash) = server.request(
+        "POST",
+        "/hash",
+        Some(json!({"inputs": ["1", "0x2"], "mode": "compress", "encoding": "hex"})),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(
+        hash,
+        json!({"outputs": ["0x2bcea035a1251603f1ceaf73cd4ae89427c47075bb8e3a944039ff1e3d6d2a6f"]})
+    );
+
+    let mimc: DynMiMC<Fr> = params::by_name("mimc-5-220-bn254").unwrap();
+    let (status, batch) = server.request(
+        "POST",
+        "/hash/batch",
+        Some(json!({"inputs": [["1"], ["1", "2", "3"]], "num_outputs": 2})),
+    );
+    assert_eq!(status, 200);
+    let mut expected = mimc.clone();
+    expected.num_outputs = 2;
+    let expected: Vec<Vec<String>> = [vec![1u64], vec![1, 2, 3]]
+        .iter()
+        .map(|inputs| {
+            expected
+                .permute(inputs.iter().map(|x| Fr::from(*x)).collect())
+                .iter()
+                .map(|f| f.to_string())
+                .collect()
+        })
+        .collect();
+    assert_eq!(batch, json!({ "outputs": expected }));
+}
+
+#[test]
+fn merkle_proofs_verify_natively() {
+    let server = Server::start(&[
+        "--preset",
+        "mimc-7-91-bn254",
+        "--listen",
+        "127.0.0.1:0",
+        "--key",
+        "7",
+    ]);
+    let (status, merkle) = server.request(
+        "POST",
+        "/merkle",
+        Some(json!({"depth": 3, "leaves": ["1", "2", "3", "4", "5"], "proofs": [4, 7]})),
+    );
+    assert_eq!(status, 200);
+
+    let preset: DynMiMC<Fr> = params::by_name("mimc-7-91-bn254").unwrap();
+    let mimc = DynMiMC::new(
+        preset.permutation_type,
+        7,
+        1,
+        Fr::from(7u64),
+        preset.round_keys().to_vec(),
+    );
+    let leaves: Vec<Fr> = (1..=5u64).map(Fr::from).collect();
+    let tree = MerkleTree::new_dyn(&mimc, 3, &leaves).unwrap();
+    assert_eq!(merkle["root"], json!(tree.root().to_string()));
+
+    let documents: Vec<MerkleProofDocument> =
+        serde_json::from_value(merkle["proofs"].clone()).unwrap();
+    for (document, index) in documents.iter().zip([4, 7]) {
+        let (proof, root) = document.to_proof::<Fr>().unwrap();
+        assert_eq!(proof, tree.proof(index).unwrap());
+        assert!(proof.verify_dyn(&mimc, root));
+
+        let (status, verified) = server.request(
+            "POST",
+            "/merkle/verify",
+            Some(serde_json::to_value(document).unwrap()),
+        );
+        assert_eq!((status, verified), (200, json!({"valid": true})));
+    }
+
+    let mut forged = serde_json::to_value(&documents[0]).unwrap();
+    forged["leaf"] = json!("6");
+    let (status, verified) = server.request("POST", "/merkle/verify", Some(forged));
+    assert_eq!((status, verified), (200, json!({"valid": false})));
+}
+
+#[test]
+fn errors() {
+    let server = Server::start(&["--preset", "mimc-7-91-bn254", "--listen", "127.0.0.1:0"]);
+    for (method, path, body, expected) in [
+        (
+            "POST",
+            "/hash",
+            json!({"inputs": ["1"], "mode": "compress"}),
+            400,
+        ),
+        ("POST", "/hash", json!({"inputs": ["not a number"]}), 400),
+        ("POST", "/hash", json!({"input": ["1"]}), 400),
+        (
+            "POST",
+            "/hash/batch",
+            json!({"inputs": [["1", "2"], ["3"]], "mode": "compress"}),
+            400,
+        ),
+        (
+            "POST",
+            "/merkle",
+            json!({"depth": 1, "leaves": ["1", "2", "3"]}),
+            400,
+        ),
+        ("POST", "/merkle", json!({"depth": 21, "leaves": []}), 400),
+        (
+            "POST",
+            "/merkle",
+            json!({"depth": 1, "leaves": [], "proofs": [2]}),
+            400,
+        ),
+        ("GET", "/hash", json!(null), 405),
+        ("POST", "/nothing", json!(null), 404),
+    ] {
+        let (status, error) = server.request(method, path, Some(body));
+        assert_eq!(status, expected, "{method} {path}: {error}");
+        assert!(error["error"].is_string());
+    }
+
+    let server = Server::start(&[
+        "--preset",
+        "mimc-7-91-bn254",
+        "--listen",
+        "127.0.0.1:0",
+        "--max-body",
+        "16",
+    ]);
+    let (status, error) = server.request("POST", "/hash", Some(json!({"inputs": ["1", "2"]})));
+    assert_eq!(status, 413, "{error}");
+    assert_eq!(server.request("GET", "/info", None).0, 200);
+
+    for args in [
+        ["--listen", "0.0.0.0:0", "--max-depth", "20"],
+        ["--listen", "127.0.0.1:0", "--max-depth", "33"],
+    ] {
+        let status = Command::new(env!("CARGO_BIN_EXE_mimc-server"))
+            .args(["--preset", "mimc-7-91-bn254"])
+            .args(args)
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(!status.success());
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn hashes_over_unix_socket() {
+    use std::os::unix::net::UnixStream;
+
+    let path = std::env::temp_dir().join(format!("mimc-server-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let server = Server::start(&[
+        "--preset",
+        "mimc-7-91-bn254",
+        "--unix",
+        path.to_str().unwrap(),
+    ]);
+    assert_eq!(server.address, path.to_str().unwrap());
+
+    let (status, hash) = request(
+        UnixStream::connect(&path).unwrap(),
+        "POST",
+        "/hash",
+        Some(json!({"inputs": ["1", "0"], "mode": "compress"})),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(
+        hash,
+        json!({"outputs": ["21581643069407877618298966131175370729897531221281133974758693417099906058024"]})
+    );
+    drop(server);
+    let _ = std::fs::remove_file(&path);
+}